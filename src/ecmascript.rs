@@ -0,0 +1,146 @@
+//! The ECMAScript `StringToNumber` conversion, as used by `Number(string)`
+//! and unary `+` in JavaScript.
+
+use super::{Chars, ParseOptions, Parser, SpecialValues};
+use std::f64;
+
+static OPTIONS : ParseOptions = ParseOptions {
+	special_values: SpecialValues {
+		infinity: &["Infinity"],
+		nan: &[],
+		signaling_nan: &[],
+		nan_payload: false,
+		ignore_case: false
+	},
+	skip_whitespace: false,
+	..ParseOptions::new()
+};
+
+/// Converts a string to a number like `Number(string)` in JavaScript.
+///
+/// This follows StringToNumber from ECMA-262:
+///
+/// * Whitespace and line terminators are trimmed from both ends. These
+///   include U+FEFF, U+2028, U+2029 and the Unicode space separators.
+/// * A string that is empty after trimming is `0`.
+/// * `Infinity` is recognized with an optional sign, but spellings like
+///   `inf` or `infinity` are not.
+/// * `0x`, `0o` and `0b` integers are recognized without a sign, and are
+///   correctly rounded when they don't fit.
+/// * Any other input must be a decimal number in its entirety, so
+///   trailing characters give NaN instead of being ignored.
+///
+/// ```
+/// use strtod::ecmascript::string_to_number;
+///
+/// assert_eq!(string_to_number(" 0x1F\n"), 31.0);
+/// assert!(string_to_number("12px").is_nan());
+/// ```
+pub fn string_to_number(input: &str) -> f64 {
+	let input = input.trim_matches(is_whitespace).as_bytes();
+	
+	if input.is_empty() {
+		return 0_f64;
+	}
+	
+	let mut parser = Parser::new(&OPTIONS);
+	
+	if input.len() > 2 && input[0] == b'0' {
+		let radix = match input[1] {
+			b'x' | b'X' => 16,
+			b'o' | b'O' => 8,
+			b'b' | b'B' => 2,
+			_ => 0
+		};
+		
+		if radix != 0 {
+			let s = Chars { bytes: input, offset: 2 };
+			
+			return if parser.integer(s, radix) && parser.end == input.len() {
+				parser.rv.d
+			} else {
+				f64::NAN
+			};
+		}
+	}
+	
+	parser.parse_strict(input, false).unwrap_or(f64::NAN)
+}
+
+// StrWhiteSpaceChar: WhiteSpace and LineTerminator.
+fn is_whitespace(c: char) -> bool {
+	matches!(c,
+		'\t' | '\n' | '\u{b}' | '\u{c}' | '\r' | ' ' | '\u{a0}' | '\u{1680}' |
+		'\u{2000}' ..= '\u{200a}' | '\u{2028}' | '\u{2029}' | '\u{202f}' |
+		'\u{205f}' | '\u{3000}' | '\u{feff}')
+}
+
+#[cfg(test)]
+mod test {
+	use super::string_to_number;
+	use test::check;
+	use std::f64;
+	
+	#[test]
+	pub fn whitespace_tests() {
+		check(string_to_number, "", 0.0);
+		check(string_to_number, " \t\n\u{a0}\u{feff}\u{2028}\u{3000}", 0.0);
+		check(string_to_number, "\u{2003}12.5\u{2029}", 12.5);
+		check(string_to_number, " +Infinity ", f64::INFINITY);
+		check(string_to_number, "1 2", f64::NAN);
+		check(string_to_number, "1\u{0}", f64::NAN);
+		check(string_to_number, "1\u{85}", f64::NAN);
+	}
+	
+	#[test]
+	pub fn decimal_tests() {
+		check(string_to_number, "-0", -0.0);
+		check(string_to_number, "+.5", 0.5);
+		check(string_to_number, "5.", 5.0);
+		check(string_to_number, "007", 7.0);
+		check(string_to_number, "1e3", 1000.0);
+		check(string_to_number, "1e400", f64::INFINITY);
+		check(string_to_number, ".", f64::NAN);
+		check(string_to_number, "+", f64::NAN);
+		check(string_to_number, "e5", f64::NAN);
+		check(string_to_number, "1e", f64::NAN);
+		check(string_to_number, "1e+", f64::NAN);
+		check(string_to_number, "12px", f64::NAN);
+		check(string_to_number, "1_000", f64::NAN);
+	}
+	
+	#[test]
+	pub fn infinity_tests() {
+		check(string_to_number, "Infinity", f64::INFINITY);
+		check(string_to_number, "-Infinity", f64::NEG_INFINITY);
+		check(string_to_number, "inf", f64::NAN);
+		check(string_to_number, "infinity", f64::NAN);
+		check(string_to_number, "INFINITY", f64::NAN);
+		check(string_to_number, "NaN", f64::NAN);
+	}
+	
+	#[test]
+	pub fn radix_tests() {
+		check(string_to_number, "0x1F", 31.0);
+		check(string_to_number, "0XfF", 255.0);
+		check(string_to_number, "0o17", 15.0);
+		check(string_to_number, "0B101", 5.0);
+		check(string_to_number, "0x", f64::NAN);
+		check(string_to_number, "-0x10", f64::NAN);
+		check(string_to_number, "+0x10", f64::NAN);
+		check(string_to_number, "0x1.8p3", f64::NAN);
+		check(string_to_number, "0b2", f64::NAN);
+		check(string_to_number, "0o8", f64::NAN);
+		check(string_to_number, "0x1g", f64::NAN);
+	}
+	
+	#[test]
+	pub fn radix_rounding_tests() {
+		// Integers beyond 53 bits round to nearest, ties to even.
+		check(string_to_number, "0x20000000000001", 9007199254740992.0);
+		check(string_to_number, "0x20000000000003", 9007199254740996.0);
+		check(string_to_number, "0x1fffffffffffff800", 36893488147419103232.0);
+		check(string_to_number, "0b11111111111111111111111111111111111111111111111111111111111111111", 36893488147419103232.0);
+		check(string_to_number, "0o7777777777777777777777", 73786976294838206464.0);
+	}
+}
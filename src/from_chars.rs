@@ -0,0 +1,230 @@
+//! `std::from_chars` from C++17 for floating point numbers.
+
+use super::{narrow, ParseOptions, Parser, Range, SpecialValues};
+
+static GENERAL : ParseOptions = ParseOptions {
+	special_values: SpecialValues::C,
+	skip_whitespace: false,
+	leading_plus: false,
+	..ParseOptions::new()
+};
+
+static FIXED : ParseOptions = ParseOptions {
+	exponent_markers: &[],
+	..GENERAL
+};
+
+/// The formats of `std::chars_format`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum CharsFormat {
+	/// A decimal number with an exponent, like `1.5e3`.
+	Scientific,
+	/// A decimal number without an exponent, like `1500`. An `e` ends
+	/// the number.
+	Fixed,
+	/// A hexadecimal number without the `0x` prefix, like `1.8p3`. The
+	/// binary exponent is optional.
+	Hex,
+	/// A decimal number with an optional exponent.
+	#[default]
+	General
+}
+
+/// The `std::errc` values reported by `from_chars`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Errc {
+	/// No number was found.
+	InvalidArgument,
+	/// The number overflows, or underflows to zero.
+	ResultOutOfRange
+}
+
+/// The `std::from_chars_result` of `from_chars`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FromCharsResult {
+	/// The number of bytes that make up the number, or `0` when no number
+	/// was found.
+	pub ptr: usize,
+	/// The error, or `None` on success.
+	pub ec: Option<Errc>
+}
+
+/// Parses a 64-bit floating point number from the start of a byte slice
+/// like `std::from_chars` in C++17.
+///
+/// The grammar is that of C `strtod`, including `inf`, `infinity`, `nan`
+/// and `nan(n-char-sequence)`, except that whitespace is not skipped, a
+/// leading `+` is not allowed, and `fmt` decides on the exponent and the
+/// radix.
+///
+/// On success `value` is set. When the number is out of range, `ptr`
+/// still covers it but `value` is left alone. When no number is found,
+/// `ptr` is `0` and `value` is left alone too.
+///
+/// ```
+/// use strtod::from_chars::{from_chars, CharsFormat, Errc};
+///
+/// let mut value = 0.0;
+///
+/// let result = from_chars(b"1.5e3", &mut value, CharsFormat::Fixed);
+/// assert_eq!((value, result.ptr, result.ec), (1.5, 3, None));
+///
+/// let result = from_chars(b"1e400", &mut value, CharsFormat::General);
+/// assert_eq!((value, result.ptr, result.ec), (1.5, 5, Some(Errc::ResultOutOfRange)));
+/// ```
+pub fn from_chars(input: &[u8], value: &mut f64, fmt: CharsFormat) -> FromCharsResult {
+	let (d, result) = parse(input, fmt, false);
+	
+	if let Some(d) = d {
+		*value = d;
+	}
+	
+	result
+}
+
+/// Parses a 32-bit floating point number from the start of a byte slice
+/// like `std::from_chars` in C++17.
+///
+/// See `from_chars` for details. The number is rounded directly to single
+/// precision, and it is out of range if it overflows or underflows as a
+/// 32-bit number.
+pub fn from_chars_f32(input: &[u8], value: &mut f32, fmt: CharsFormat) -> FromCharsResult {
+	let (d, result) = parse(input, fmt, true);
+	
+	if let Some(d) = d {
+		*value = narrow(d);
+	}
+	
+	result
+}
+
+fn parse(input: &[u8], fmt: CharsFormat, single: bool) -> (Option<f64>, FromCharsResult) {
+	let invalid = FromCharsResult { ptr: 0, ec: Some(Errc::InvalidArgument) };
+	
+	let mut parser = Parser::new(if fmt == CharsFormat::Fixed { &FIXED } else { &GENERAL });
+	parser.single = single;
+	parser.assume_hex = fmt == CharsFormat::Hex;
+	
+	let d = match parser.parse(input) {
+		Ok(d) => d,
+		Err(_) => return (None, invalid)
+	};
+	
+	let number = &input[parser.start..parser.end];
+	
+	if fmt == CharsFormat::Scientific && !number[0].is_ascii_alphabetic()
+		&& !number.iter().any(|&c| c == b'e' || c == b'E') {
+		// The exponent is required; an e without digits was backed off.
+		return (None, invalid);
+	}
+	
+	match parser.range {
+		Range::Overflow | Range::Underflow => (None, FromCharsResult {
+			ptr: parser.end,
+			ec: Some(Errc::ResultOutOfRange)
+		}),
+		_ => (Some(d), FromCharsResult { ptr: parser.end, ec: None })
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{from_chars, from_chars_f32, CharsFormat, Errc};
+	use super::CharsFormat::*;
+	use test::check;
+	use std::f64;
+	
+	#[test]
+	pub fn general_tests() {
+		check(with(General), "12.5x", (12.5, 4, None));
+		check(with(General), "-0", (-0.0, 2, None));
+		check(with(General), ".5", (0.5, 2, None));
+		check(with(General), "5.", (5.0, 2, None));
+		check(with(General), "1E5", (100000.0, 3, None));
+		check(with(General), "1e", (1.0, 1, None));
+		check(with(General), "1e+", (1.0, 1, None));
+		check(with(General), "1p3", (1.0, 1, None));
+		check(with(General), "0x1p3", (0.0, 1, None));
+		check(with(General), "inf", (f64::INFINITY, 3, None));
+		check(with(General), "-INFINITY", (f64::NEG_INFINITY, 9, None));
+		check(with(General), "nan(0x12)", (f64::from_bits(0x7ff8000000000012), 9, None));
+	}
+	
+	#[test]
+	pub fn invalid_argument_tests() {
+		check(with(General), "+1", (1.5, 0, Some(Errc::InvalidArgument)));
+		check(with(General), " 1", (1.5, 0, Some(Errc::InvalidArgument)));
+		check(with(General), "", (1.5, 0, Some(Errc::InvalidArgument)));
+		check(with(General), "-", (1.5, 0, Some(Errc::InvalidArgument)));
+		check(with(General), ".", (1.5, 0, Some(Errc::InvalidArgument)));
+		check(with(General), "e5", (1.5, 0, Some(Errc::InvalidArgument)));
+	}
+	
+	#[test]
+	pub fn scientific_tests() {
+		check(with(Scientific), "1.5e3", (1500.0, 5, None));
+		check(with(Scientific), "-1E-1x", (-0.1, 5, None));
+		check(with(Scientific), "inf", (f64::INFINITY, 3, None));
+		check(with(Scientific), "1.5", (1.5, 0, Some(Errc::InvalidArgument)));
+		check(with(Scientific), "1e", (1.5, 0, Some(Errc::InvalidArgument)));
+		check(with(Scientific), "1e+x", (1.5, 0, Some(Errc::InvalidArgument)));
+	}
+	
+	#[test]
+	pub fn fixed_tests() {
+		check(with(Fixed), "1.5e3", (1.5, 3, None));
+		check(with(Fixed), "1e400", (1.0, 1, None));
+		check(with(Fixed), "-nan", (-f64::NAN, 4, None));
+	}
+	
+	#[test]
+	pub fn hex_tests() {
+		check(with(Hex), "1.8p3", (12.0, 5, None));
+		check(with(Hex), "ff.8p-1", (127.75, 7, None));
+		check(with(Hex), "1e400", (123904.0, 5, None));
+		check(with(Hex), "-.8", (-0.5, 3, None));
+		check(with(Hex), "1p", (1.0, 1, None));
+		check(with(Hex), "0x1p3", (0.0, 1, None));
+		check(with(Hex), "inf", (f64::INFINITY, 3, None));
+		check(with(Hex), "p3", (1.5, 0, Some(Errc::InvalidArgument)));
+		check(with(Hex), "+1", (1.5, 0, Some(Errc::InvalidArgument)));
+	}
+	
+	#[test]
+	pub fn out_of_range_tests() {
+		check(with(General), "1e400", (1.5, 5, Some(Errc::ResultOutOfRange)));
+		check(with(General), "-1e400", (1.5, 6, Some(Errc::ResultOutOfRange)));
+		check(with(General), "1e-400", (1.5, 6, Some(Errc::ResultOutOfRange)));
+		check(with(Hex), "1p-1080", (1.5, 7, Some(Errc::ResultOutOfRange)));
+		check(with(Hex), "1p1024", (1.5, 6, Some(Errc::ResultOutOfRange)));
+		check(with(General), "4.9e-324", (5e-324, 8, None));
+		check(with(General), "2.4703282292062328e-324", (5e-324, 23, None));
+		check(with(General), "2.4703282292062327e-324", (1.5, 23, Some(Errc::ResultOutOfRange)));
+		check(with(General), "0e-999", (0.0, 6, None));
+	}
+	
+	#[test]
+	pub fn single_tests() {
+		check(single(General), "0.1", (0.1, 3, None));
+		check(single(General), "1e-45", (1e-45, 5, None));
+		check(single(General), "3.4028236e38", (1.5, 12, Some(Errc::ResultOutOfRange)));
+		check(single(General), "1e-46", (1.5, 5, Some(Errc::ResultOutOfRange)));
+	}
+	
+	// The value starts out as 1.5 to show when it is left alone.
+	fn with(fmt: CharsFormat) -> impl Fn(&str) -> (f64, usize, Option<Errc>) {
+		move |input| {
+			let mut value = 1.5;
+			let result = from_chars(input.as_bytes(), &mut value, fmt);
+			(value, result.ptr, result.ec)
+		}
+	}
+	
+	fn single(fmt: CharsFormat) -> impl Fn(&str) -> (f32, usize, Option<Errc>) {
+		move |input| {
+			let mut value = 1.5;
+			let result = from_chars_f32(input.as_bytes(), &mut value, fmt);
+			(value, result.ptr, result.ec)
+		}
+	}
+}
@@ -0,0 +1,189 @@
+//! A `strtod` that gives the same results as the one in glibc, for
+//! porting C code that depends on its exact behavior.
+
+use super::{ParseOptions, Parser, Range, SpecialValues};
+
+static OPTIONS : ParseOptions = ParseOptions {
+	special_values: SpecialValues::C,
+	hex_floats: true,
+	..ParseOptions::new()
+};
+
+/// What C `strtod` reports through its return value, `endptr` and `errno`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Output {
+	/// The return value.
+	pub value: f64,
+	/// The number of bytes consumed, i.e. `endptr - nptr`.
+	pub end: usize,
+	/// Whether `errno` is set to `ERANGE`.
+	pub erange: bool
+}
+
+/// Parses a 64-bit floating point number from the start of a byte slice
+/// like glibc `strtod` in the "C" locale.
+///
+/// Like the default `strtod_prefix`, leading ASCII whitespace is skipped,
+/// only `.` is a decimal point regardless of the process locale, and an
+/// exponent without digits is backed off, so only the `1` of `"1e"` and
+/// `"1e+"` is consumed. The differences from the default mode are:
+///
+/// * Hexadecimal floating point numbers like `0x1.8p3` are accepted. A
+///   `0x` without hexadecimal digits is parsed as the `0` in front of it.
+/// * `inf`, `infinity`, `nan` and `nan(n-char-sequence)` are accepted in
+///   any case, with the payload read as by `strtoull` with base 0.
+/// * When no number is found, `value` is `0` and `end` is `0`, instead of
+///   an error.
+/// * `erange` is set when the result overflows to infinity, and when it
+///   is inexact and tiny: zero, subnormal, or rounded up to the smallest
+///   normal number from below the halfway point with full precision. As
+///   glibc reads NaN payloads with `strtoull`, it is also set when the
+///   number at the start of the n-char-sequence does not fit in 64 bits.
+///
+/// Some versions of glibc lose the bits below the rounding bit of very
+/// long subnormal inputs, and then round them down and don't report
+/// ERANGE. The result here is correctly rounded instead.
+///
+/// ```
+/// use strtod::glibc::strtod;
+///
+/// let output = strtod(b" 0x1.8p3e");
+///
+/// assert_eq!(output.value, 12.0);
+/// assert_eq!(output.end, 8);
+/// assert!(!output.erange);
+/// ```
+pub fn strtod(input: &[u8]) -> Output {
+	let mut parser = Parser::new(&OPTIONS);
+	
+	let conversion = match parser.convert(input) {
+		Ok(conversion) => conversion,
+		Err(_) => return Output { value: 0_f64, end: 0, erange: false }
+	};
+	
+	let end = parser.end;
+	let erange = if conversion.value.is_nan() {
+		input[end - 1] == b')' && {
+			let start = input[..end].iter().rposition(|&c| c == b'(').unwrap() + 1;
+			payload_overflows(&input[start..end - 1])
+		}
+	} else {
+		conversion.range == Range::Overflow || (parser.tiny && !conversion.is_exact())
+	};
+	
+	Output { value: conversion.value, end, erange }
+}
+
+// Whether strtoull with base 0 overflows on the number at the start of the
+// sequence.
+fn payload_overflows(seq: &[u8]) -> bool {
+	let (digits, radix) = if seq.len() > 2 && seq[0] == b'0' && (seq[1] == b'x' || seq[1] == b'X') {
+		(&seq[2..], 16)
+	} else if !seq.is_empty() && seq[0] == b'0' {
+		(seq, 8)
+	} else {
+		(seq, 10)
+	};
+	
+	let mut payload = 0_u64;
+	
+	for &c in digits {
+		let digit = match (c as char).to_digit(radix) {
+			Some(digit) => digit,
+			None => break
+		};
+		
+		payload = match payload.checked_mul(radix as u64).and_then(|payload| payload.checked_add(digit as u64)) {
+			Some(payload) => payload,
+			None => return true
+		};
+	}
+	
+	false
+}
+
+#[cfg(test)]
+mod test {
+	use super::{strtod, Output};
+	use test::check;
+	use std::f64;
+	
+	#[test]
+	pub fn prefix_tests() {
+		check(parse, "  12.5abc", (12.5, 6, false));
+		check(parse, "-0", (-0.0, 2, false));
+		check(parse, "1e", (1.0, 1, false));
+		check(parse, "1e+", (1.0, 1, false));
+		check(parse, "1e+x", (1.0, 1, false));
+		check(parse, "1,5", (1.0, 1, false));
+	}
+	
+	#[test]
+	pub fn no_number_tests() {
+		check(parse, "", (0.0, 0, false));
+		check(parse, " ", (0.0, 0, false));
+		check(parse, "-", (0.0, 0, false));
+		check(parse, ".", (0.0, 0, false));
+		check(parse, "e5", (0.0, 0, false));
+		check(parse, "-x", (0.0, 0, false));
+	}
+	
+	#[test]
+	pub fn hex_tests() {
+		check(parse, "0x1.8p3", (12.0, 7, false));
+		check(parse, "-0X1P-2", (-0.25, 7, false));
+		check(parse, "0x1p", (1.0, 3, false));
+		check(parse, "0x", (0.0, 1, false));
+		check(parse, "0x.p1", (0.0, 1, false));
+		check(parse, "0xg", (0.0, 1, false));
+		check(parse, "0x1.fffffffffffff8p0", (2.0, 20, false));
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		check(parse, "inf", (f64::INFINITY, 3, false));
+		check(parse, "-Infinity", (f64::NEG_INFINITY, 9, false));
+		check(parse, "infinit", (f64::INFINITY, 3, false));
+		check(parse, "nan", (f64::NAN, 3, false));
+		check(parse, "-NaN", (-f64::NAN, 4, false));
+	}
+	
+	#[test]
+	pub fn nan_payload_tests() {
+		check(parse, "nan(0x1234)", (f64::from_bits(0x7ff8000000001234), 11, false));
+		check(parse, "nan(1_2)", (f64::NAN, 8, false));
+		check(parse, "nan(12", (f64::NAN, 3, false));
+		check(parse, "nan(18446744073709551615)", (f64::from_bits(0x7fffffffffffffff), 25, false));
+		check(parse, "nan(18446744073709551616)", (f64::from_bits(0x7fffffffffffffff), 25, true));
+		check(parse, "-nan(0x1ffffffffffffffff)", (f64::from_bits(0xffffffffffffffff), 25, true));
+		check(parse, "nan(99999999999999999999x)", (f64::NAN, 26, true));
+		check(parse, "nan(_99999999999999999999)", (f64::NAN, 26, false));
+	}
+	
+	#[test]
+	pub fn erange_tests() {
+		check(parse, "1e309", (f64::INFINITY, 5, true));
+		check(parse, "-0x1p1024", (f64::NEG_INFINITY, 9, true));
+		check(parse, "1e-400", (0.0, 6, true));
+		check(parse, "-1e-400", (-0.0, 7, true));
+		check(parse, "4.9406564584124654e-324", (5e-324, 23, true));
+		check(parse, "0x1p-1074", (5e-324, 9, false));
+		check(parse, "0x1.8p-1074", (1e-323, 11, true));
+		check(parse, "2.2250738585072011e-308", (2.225073858507201e-308, 23, true));
+		check(parse, "2.2250738585072014e-308", (f64::MIN_POSITIVE, 23, false));
+		
+		// Rounded up to the smallest normal number from either side of
+		// the halfway point between it and the number below it with full
+		// precision, and from the halfway point itself.
+		check(parse, "0x1.fffffffffffffp-1023", (f64::MIN_POSITIVE, 23, true));
+		check(parse, "0x1.fffffffffffff7ffp-1023", (f64::MIN_POSITIVE, 26, true));
+		check(parse, "0x1.fffffffffffff8p-1023", (f64::MIN_POSITIVE, 24, false));
+		check(parse, "2.2250738585072012e-308", (f64::MIN_POSITIVE, 23, true));
+		check(parse, "2.2250738585072013e-308", (f64::MIN_POSITIVE, 23, false));
+	}
+	
+	fn parse(input: &str) -> (f64, usize, bool) {
+		let Output { value, end, erange } = strtod(input.as_bytes());
+		(value, end, erange)
+	}
+}
@@ -0,0 +1,270 @@
+//! `strconv.ParseFloat` from Go.
+
+use super::{GroupSeparators, ParseOptions, Parser, Range, SpecialValues};
+use std::error;
+use std::fmt;
+
+// Underscores are skipped anywhere, and then checked the way Go does.
+static OPTIONS : ParseOptions = ParseOptions {
+	special_values: SpecialValues {
+		infinity: &["infinity", "inf"],
+		nan: &["nan"],
+		signaling_nan: &[],
+		nan_payload: false,
+		ignore_case: true
+	},
+	hex_floats: true,
+	group_separators: GroupSeparators {
+		characters: &['_'],
+		leading: true,
+		trailing: true,
+		consecutive: true
+	},
+	skip_whitespace: false,
+	exponent_backoff: false,
+	..ParseOptions::new()
+};
+
+// math.NaN()
+const NAN_BITS : u64 = 0x7ff8000000000001;
+
+/// The errors of `strconv.ParseFloat`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NumError {
+	/// `strconv.ErrSyntax`: the input is not a number.
+	Syntax,
+	/// `strconv.ErrRange`: the number overflows.
+	Range
+}
+
+impl fmt::Display for NumError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match *self {
+			NumError::Syntax => "invalid syntax",
+			NumError::Range => "value out of range"
+		})
+	}
+}
+
+impl error::Error for NumError {}
+
+/// Parses a floating point number like `strconv.ParseFloat(s, bitSize)`
+/// in Go.
+///
+/// With a `bit_size` of 32 the number is rounded directly to single
+/// precision and returned as an `f64`; any other size means 64, as in Go.
+///
+/// The grammar is that of Go floating point literals with an optional
+/// sign:
+///
+/// * Whitespace is not skipped, and nothing may follow the number.
+/// * `inf` and `infinity` are accepted in any case with an optional sign,
+///   and `nan` in any case without one. NaN is `math.NaN()`, which has the
+///   bits `0x7FF8000000000001`, or the default NaN when `bit_size` is 32.
+/// * Hexadecimal numbers like `0x1.8p3` must have the binary exponent.
+/// * Underscores are allowed between two digits in decimal and
+///   hexadecimal numbers, and between the `0x` prefix and a digit, like
+///   `1_000.5` or `0x_1p-2`. This follows `underscoreOK` in Go, which
+///   also accepts them in numbers without a base prefix, so `1_000` is
+///   `1000`.
+/// * The exponent must have digits.
+///
+/// Like Go, the result comes with an error instead of in place of one:
+/// `0` with `NumError::Syntax`, or the infinity with the sign of the
+/// number with `NumError::Range` when it overflows. Numbers that underflow
+/// are rounded to zero without an error.
+///
+/// ```
+/// use strtod::go::{parse_float, NumError};
+///
+/// assert_eq!(parse_float("0x1.8p3", 64), (12.0, None));
+/// assert_eq!(parse_float("1e39", 32), (f64::INFINITY, Some(NumError::Range)));
+/// assert_eq!(parse_float("1e", 64), (0.0, Some(NumError::Syntax)));
+/// ```
+pub fn parse_float(s: &str, bit_size: u32) -> (f64, Option<NumError>) {
+	let syntax = (0_f64, Some(NumError::Syntax));
+	let input = s.as_bytes();
+	
+	let mut parser = Parser::new(&OPTIONS);
+	parser.single = bit_size == 32;
+	
+	let d = match parser.parse_strict(input, false) {
+		Ok(d) => d,
+		Err(_) => return syntax
+	};
+	
+	if d.is_nan() {
+		if parser.start != 0 {
+			return syntax;
+		}
+		
+		return (if parser.single { f64::NAN } else { f64::from_bits(NAN_BITS) }, None);
+	}
+	
+	let number = &input[parser.start..];
+	
+	if number.len() > 1 && number[0] == b'0' && (number[1] == b'x' || number[1] == b'X')
+		&& !number.iter().any(|&c| c == b'p' || c == b'P') {
+		return syntax;
+	}
+	
+	if !underscore_ok(input) {
+		return syntax;
+	}
+	
+	if parser.range == Range::Overflow {
+		(d, Some(NumError::Range))
+	} else {
+		(d, None)
+	}
+}
+
+// A port of underscoreOK from Go: underscores must be between two digits,
+// where the base prefix counts as a digit.
+fn underscore_ok(mut s: &[u8]) -> bool {
+	// The last character class: ^ for the start, 0 for a digit or the base
+	// prefix, _ for an underscore and ! for anything else.
+	let mut saw = b'^';
+	let mut i = 0;
+	
+	if !s.is_empty() && (s[0] == b'-' || s[0] == b'+') {
+		s = &s[1..];
+	}
+	
+	let mut hex = false;
+	if s.len() >= 2 && s[0] == b'0' && matches!(s[1].to_ascii_lowercase(), b'b' | b'o' | b'x') {
+		i = 2;
+		saw = b'0';
+		hex = s[1].eq_ignore_ascii_case(&b'x');
+	}
+	
+	while i < s.len() {
+		let c = s[i];
+		i += 1;
+		
+		if c.is_ascii_digit() || (hex && c.is_ascii_hexdigit()) {
+			saw = b'0';
+		} else if c == b'_' {
+			if saw != b'0' {
+				return false;
+			}
+			saw = b'_';
+		} else if saw == b'_' {
+			return false;
+		} else {
+			saw = b'!';
+		}
+	}
+	
+	saw != b'_'
+}
+
+#[cfg(test)]
+mod test {
+	use super::{parse_float, NumError};
+	use test::check;
+	use std::f64;
+	
+	#[test]
+	pub fn decimal_tests() {
+		check(parse64, "1", (1.0, None));
+		check(parse64, "+1", (1.0, None));
+		check(parse64, "-0", (-0.0, None));
+		check(parse64, ".5", (0.5, None));
+		check(parse64, "5.", (5.0, None));
+		check(parse64, "007", (7.0, None));
+		check(parse64, "1e23", (1e+23, None));
+		check(parse64, "1E-1", (0.1, None));
+		check(parse64, "1e-400", (0.0, None));
+		check(parse64, "", (0.0, Some(NumError::Syntax)));
+		check(parse64, " 1", (0.0, Some(NumError::Syntax)));
+		check(parse64, "1 ", (0.0, Some(NumError::Syntax)));
+		check(parse64, ".", (0.0, Some(NumError::Syntax)));
+		check(parse64, "+", (0.0, Some(NumError::Syntax)));
+		check(parse64, "1e", (0.0, Some(NumError::Syntax)));
+		check(parse64, "1e+", (0.0, Some(NumError::Syntax)));
+		check(parse64, "1x", (0.0, Some(NumError::Syntax)));
+		check(parse64, "1p1", (0.0, Some(NumError::Syntax)));
+	}
+	
+	#[test]
+	pub fn hex_tests() {
+		check(parse64, "0x1p-2", (0.25, None));
+		check(parse64, "0X1.8P3", (12.0, None));
+		check(parse64, "-0x.8p1", (-1.0, None));
+		check(parse64, "0x1.fffffffffffff8p0", (2.0, None));
+		check(parse64, "0x1p-1075", (0.0, None));
+		check(parse64, "0x1.000001p-1074", (5e-324, None));
+		check(parse64, "0x", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x1", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x1.8", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x1p", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0xp1", (0.0, Some(NumError::Syntax)));
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		check(parse64, "inf", (f64::INFINITY, None));
+		check(parse64, "-Inf", (f64::NEG_INFINITY, None));
+		check(parse64, "+INFINITY", (f64::INFINITY, None));
+		check(parse64, "NaN", (f64::from_bits(0x7ff8000000000001), None));
+		check(parse32, "nan", (f64::NAN, None));
+		check(parse64, "infinit", (0.0, Some(NumError::Syntax)));
+		check(parse64, "infinityx", (0.0, Some(NumError::Syntax)));
+		check(parse64, "+nan", (0.0, Some(NumError::Syntax)));
+		check(parse64, "-NaN", (0.0, Some(NumError::Syntax)));
+		check(parse64, "nan(1)", (0.0, Some(NumError::Syntax)));
+	}
+	
+	#[test]
+	pub fn underscore_tests() {
+		check(parse64, "1_23.50_0_0e+1_2", (123500000000000.0, None));
+		check(parse64, "0x_1_2.3_4_5p+1_2", (74565.0, None));
+		check(parse64, "1_000", (1000.0, None));
+		check(parse64, "-_123.5e+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "_123.5e+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "1__23.5e+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "123_.5e+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "123._5e+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "123.5_e+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "123.5e_+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "123.5e+_12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "123.5e+1__2", (0.0, Some(NumError::Syntax)));
+		check(parse64, "123.5e+12_", (0.0, Some(NumError::Syntax)));
+		check(parse64, "-_0x12.345p+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x__12.345p+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x12_.345p+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x12._345p+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x12.345_p+12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x12.345p+_12", (0.0, Some(NumError::Syntax)));
+		check(parse64, "0x12.345p+12_", (0.0, Some(NumError::Syntax)));
+	}
+	
+	#[test]
+	pub fn bit_size_32_tests() {
+		check(parse32, "0.1", (f64::from(0.1_f32), None));
+		check(parse32, "1.00000017881393432617187499", (f64::from(1.0000001_f32), None));
+		check(parse32, "1e-46", (0.0, None));
+		check(parse32, "0x1.fffffep127", (f64::from(f32::MAX), None));
+		check(parse32, "3.4028235e38", (f64::from(f32::MAX), None));
+	}
+	
+	#[test]
+	pub fn range_tests() {
+		check(parse64, "1e309", (f64::INFINITY, Some(NumError::Range)));
+		check(parse64, "-0x1p1024", (f64::NEG_INFINITY, Some(NumError::Range)));
+		check(parse64, "1.7976931348623159e308", (f64::INFINITY, Some(NumError::Range)));
+		check(parse64, "1.7976931348623158e308", (f64::MAX, None));
+		check(parse32, "-3.4028236e38", (f64::NEG_INFINITY, Some(NumError::Range)));
+		check(parse32, "0x1p128", (f64::INFINITY, Some(NumError::Range)));
+		check(parse64, "1e309x", (0.0, Some(NumError::Syntax)));
+	}
+	
+	fn parse64(s: &str) -> (f64, Option<NumError>) {
+		parse_float(s, 64)
+	}
+	
+	fn parse32(s: &str) -> (f64, Option<NumError>) {
+		parse_float(s, 32)
+	}
+}
@@ -0,0 +1,176 @@
+//! `Double.parseDouble` and `Float.parseFloat` from Java.
+
+use super::{narrow, ParseError, ParseOptions, Parser, SpecialValues};
+use std::f64;
+
+static OPTIONS : ParseOptions = ParseOptions {
+	special_values: SpecialValues {
+		infinity: &["Infinity"],
+		nan: &["NaN"],
+		signaling_nan: &[],
+		nan_payload: false,
+		ignore_case: false
+	},
+	hex_floats: true,
+	skip_whitespace: false,
+	exponent_backoff: false,
+	..ParseOptions::new()
+};
+
+/// Parses a 64-bit floating point number like `Double.parseDouble` in
+/// Java.
+///
+/// The grammar differs from the default one in these ways:
+///
+/// * Every character up to U+0020 is trimmed from both ends like with
+///   `String.trim`, including control characters like NUL, but not
+///   non-ASCII whitespace.
+/// * Nothing may follow the number, except for one of the type suffixes
+///   `f`, `F`, `d` or `D`, which are ignored: `"1.5f"` parses as 1.5 here
+///   and `"2d"` does with `parse_float`.
+/// * `NaN` and `Infinity` are accepted with an optional sign, but only in
+///   this case and without a suffix. NaN is always the positive default
+///   NaN, even for `-NaN`.
+/// * Hexadecimal numbers like `0x1.8p3` are accepted, and their binary
+///   exponent is required. Its absence is reported as
+///   `ParseError::MissingExponent`.
+/// * An exponent marker must be followed by digits.
+///
+/// Errors carry byte offsets into `s`.
+///
+/// ```
+/// use strtod::java::parse_double;
+///
+/// assert_eq!(parse_double(" 1.5d\n"), Ok(1.5));
+/// assert_eq!(parse_double("0x1.0p3"), Ok(8.0));
+/// assert!(parse_double("0x1.0").is_err());
+/// ```
+pub fn parse_double(s: &str) -> Result<f64, ParseError> {
+	parse(s, false)
+}
+
+/// Parses a 32-bit floating point number like `Float.parseFloat` in Java.
+///
+/// The number is rounded directly to single precision. See `parse_double`
+/// for the grammar.
+pub fn parse_float(s: &str) -> Result<f32, ParseError> {
+	parse(s, true).map(narrow)
+}
+
+fn parse(s: &str, single: bool) -> Result<f64, ParseError> {
+	let trimmed = s.trim_start_matches(|c| c <= ' ');
+	let start = s.len() - trimmed.len();
+	let input = trimmed.trim_end_matches(|c| c <= ' ').as_bytes();
+	
+	let mut parser = Parser::new(&OPTIONS);
+	parser.single = single;
+	
+	let d = parser.parse(input).map_err(|error| error.with_offset(start + error.offset()))?;
+	
+	let number = &input[parser.start..parser.end];
+	let mut end = parser.end;
+	
+	if number[0].is_ascii_alphabetic() {
+		// NaN or Infinity
+		if end < input.len() {
+			return Err(ParseError::InvalidCharacter(start + end));
+		}
+		
+		return Ok(if d.is_nan() { f64::NAN } else { d });
+	}
+	
+	if number.len() > 2 && (number[1] == b'x' || number[1] == b'X')
+		&& !number.iter().any(|&c| c == b'p' || c == b'P') {
+		return Err(ParseError::MissingExponent(start + end));
+	}
+	
+	if end < input.len() && matches!(input[end], b'f' | b'F' | b'd' | b'D') {
+		end += 1;
+	}
+	
+	if end < input.len() {
+		return Err(ParseError::InvalidCharacter(start + end));
+	}
+	
+	Ok(d)
+}
+
+#[cfg(test)]
+mod test {
+	use super::{parse_double, parse_float};
+	use test::check;
+	use ParseError;
+	use std::{f32, f64};
+	
+	#[test]
+	pub fn decimal_tests() {
+		check(parse_double, "1", Ok(1.0));
+		check(parse_double, "-0", Ok(-0.0));
+		check(parse_double, "+.5", Ok(0.5));
+		check(parse_double, "1.", Ok(1.0));
+		check(parse_double, "1.e5", Ok(100000.0));
+		check(parse_double, "007", Ok(7.0));
+		check(parse_double, "1e400", Ok(f64::INFINITY));
+		check(parse_double, "1e2147483648", Ok(f64::INFINITY));
+		check(parse_double, "1e-400", Ok(0.0));
+		check(parse_double, "", Err(ParseError::Empty(0)));
+		check(parse_double, ".", Err(ParseError::NoDigits(0)));
+		check(parse_double, "1e", Err(ParseError::DanglingExponent(1)));
+		check(parse_double, "1_000", Err(ParseError::InvalidCharacter(1)));
+	}
+	
+	#[test]
+	pub fn suffix_tests() {
+		check(parse_double, "1f", Ok(1.0));
+		check(parse_double, "1.5d", Ok(1.5));
+		check(parse_double, "1e5D", Ok(100000.0));
+		check(parse_double, "1e+5F", Ok(100000.0));
+		check(parse_double, ".d", Err(ParseError::NoDigits(0)));
+		check(parse_double, "1ef", Err(ParseError::DanglingExponent(1)));
+		check(parse_double, "1fd", Err(ParseError::InvalidCharacter(2)));
+		check(parse_double, "1 f", Err(ParseError::InvalidCharacter(1)));
+	}
+	
+	#[test]
+	pub fn whitespace_tests() {
+		check(parse_double, "\u{0} 1\t\u{1f}", Ok(1.0));
+		check(parse_double, " 1.5f ", Ok(1.5));
+		check(parse_double, " \u{0}", Err(ParseError::Empty(2)));
+		check(parse_double, "\u{a0}1", Err(ParseError::InvalidCharacter(0)));
+	}
+	
+	#[test]
+	pub fn hex_tests() {
+		check(parse_double, "0x1p1f", Ok(2.0));
+		check(parse_double, "-0x1p1d", Ok(-2.0));
+		check(parse_double, "0x.8p1", Ok(1.0));
+		check(parse_double, "0x1.p1", Ok(2.0));
+		check(parse_double, "0X1P-1074", Ok(5e-324));
+		check(parse_double, " 0x1", Err(ParseError::MissingExponent(4)));
+		check(parse_double, "0x1.8f", Err(ParseError::MissingExponent(6)));
+		check(parse_double, "0x1pf", Err(ParseError::DanglingExponent(3)));
+		check(parse_double, "0x.p1", Err(ParseError::InvalidCharacter(1)));
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		check(parse_double, "Infinity", Ok(f64::INFINITY));
+		check(parse_double, "-Infinity", Ok(f64::NEG_INFINITY));
+		check(parse_double, " +Infinity ", Ok(f64::INFINITY));
+		check(parse_double, "NaN", Ok(f64::NAN));
+		check(parse_double, "-NaN", Ok(f64::NAN));
+		check(parse_double, "nan", Err(ParseError::InvalidCharacter(0)));
+		check(parse_double, "Inf", Err(ParseError::InvalidCharacter(0)));
+		check(parse_double, "Infinityf", Err(ParseError::InvalidCharacter(8)));
+	}
+	
+	#[test]
+	pub fn float_tests() {
+		check(parse_float, "0.1", Ok(0.1));
+		check(parse_float, "1.00000017881393432617187499", Ok(1.0000001));
+		check(parse_float, "2d", Ok(2.0));
+		check(parse_float, "3.4028236e38", Ok(f32::INFINITY));
+		check(parse_float, "340282356779733661637539395458142568447", Ok(f32::MAX));
+		check(parse_float, "-NaN", Ok(f32::NAN));
+	}
+}
@@ -0,0 +1,123 @@
+//! Numbers in JSON as specified by RFC 8259.
+
+use super::{ParseError, ParseOptions, Parser};
+
+static OPTIONS : ParseOptions = ParseOptions {
+	skip_whitespace: false,
+	leading_plus: false,
+	leading_decimal_point: false,
+	trailing_decimal_point: false,
+	leading_zeros: false,
+	exponent_backoff: false,
+	..ParseOptions::new()
+};
+
+/// Parses a JSON number from the start of a byte slice.
+///
+/// The number must match the grammar of RFC 8259:
+///
+/// ```text
+/// number = [ minus ] int [ frac ] [ exp ]
+/// int    = zero / ( digit1-9 *DIGIT )
+/// frac   = decimal-point 1*DIGIT
+/// exp    = e [ minus / plus ] 1*DIGIT
+/// ```
+///
+/// So there is no leading `+`, no leading zeros, no whitespace, and the
+/// decimal point and the exponent marker must be followed by digits.
+/// There are no special values.
+///
+/// Returns the correctly rounded value and the length of the number in
+/// bytes, both from the same pass over the input. The number ends at the
+/// first byte that cannot continue it, and checking that byte is left to
+/// the tokenizer, so `1.5,` gives the length 3. Numbers too large for an
+/// `f64` are infinite.
+///
+/// ```
+/// use strtod::json::parse_number;
+/// use strtod::ParseError;
+///
+/// assert_eq!(parse_number(b"-1.5e3, 2]"), Ok((-1500.0, 6)));
+/// assert_eq!(parse_number(b"01"), Err(ParseError::LeadingZero(0)));
+/// assert_eq!(parse_number(b".5"), Err(ParseError::NoDigits(0)));
+/// ```
+pub fn parse_number(input: &[u8]) -> Result<(f64, usize), ParseError> {
+	let mut parser = Parser::new(&OPTIONS);
+	let d = parser.parse(input)?;
+	
+	Ok((d, parser.end))
+}
+
+#[cfg(test)]
+mod test {
+	use super::parse_number;
+	use test::check;
+	use ParseError;
+	use std::f64;
+	
+	#[test]
+	pub fn number_tests() {
+		check(parse, "0", Ok((0.0, 1)));
+		check(parse, "-0", Ok((-0.0, 2)));
+		check(parse, "1", Ok((1.0, 1)));
+		check(parse, "-12.5", Ok((-12.5, 5)));
+		check(parse, "0.1", Ok((0.1, 3)));
+		check(parse, "0e5", Ok((0.0, 3)));
+		check(parse, "0.0e-0", Ok((0.0, 6)));
+		check(parse, "1E+2", Ok((100.0, 4)));
+		check(parse, "1e-2", Ok((0.01, 4)));
+		check(parse, "1e023", Ok((1e+23, 5)));
+		check(parse, "123456789012345678901234567890", Ok((1.2345678901234568e+29, 30)));
+		check(parse, "2.2250738585072011e-308", Ok((2.225073858507201e-308, 23)));
+		check(parse, "1e400", Ok((f64::INFINITY, 5)));
+		check(parse, "-1e-400", Ok((-0.0, 7)));
+		check(parse, "", Err(ParseError::Empty(0)));
+		check(parse, "-", Err(ParseError::SignWithoutDigits(1)));
+		check(parse, "+1", Err(ParseError::InvalidCharacter(0)));
+		check(parse, " 1", Err(ParseError::InvalidCharacter(0)));
+	}
+	
+	#[test]
+	pub fn length_tests() {
+		check(parse, "1.5,", Ok((1.5, 3)));
+		check(parse, "1]", Ok((1.0, 1)));
+		check(parse, "2 ", Ok((2.0, 1)));
+		check(parse, "0x10", Ok((0.0, 1)));
+		check(parse, "1.5.5", Ok((1.5, 3)));
+		check(parse, "7_0", Ok((7.0, 1)));
+		check(parse, "1f", Ok((1.0, 1)));
+	}
+	
+	#[test]
+	pub fn decimal_point_tests() {
+		check(parse, ".", Err(ParseError::NoDigits(0)));
+		check(parse, ".5", Err(ParseError::NoDigits(0)));
+		check(parse, "-.5", Err(ParseError::NoDigits(1)));
+		check(parse, "5.", Err(ParseError::NoDigits(2)));
+		check(parse, "5.e3", Err(ParseError::NoDigits(2)));
+		check(parse, "5.]", Err(ParseError::NoDigits(2)));
+	}
+	
+	#[test]
+	pub fn leading_zero_tests() {
+		check(parse, "01", Err(ParseError::LeadingZero(0)));
+		check(parse, "-00.5", Err(ParseError::LeadingZero(1)));
+	}
+	
+	#[test]
+	pub fn exponent_tests() {
+		check(parse, "1e", Err(ParseError::DanglingExponent(1)));
+		check(parse, "1e+,", Err(ParseError::DanglingExponent(1)));
+		check(parse, "e5", Err(ParseError::NoDigits(0)));
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		check(parse, "Infinity", Err(ParseError::InvalidCharacter(0)));
+		check(parse, "-NaN", Err(ParseError::SignWithoutDigits(1)));
+	}
+	
+	fn parse(input: &str) -> Result<(f64, usize), ParseError> {
+		parse_number(input.as_bytes())
+	}
+}
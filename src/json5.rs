@@ -0,0 +1,139 @@
+//! Numbers in JSON5.
+
+use super::{Chars, ParseError, ParseOptions, Parser, SpecialValues};
+
+static OPTIONS : ParseOptions = ParseOptions {
+	special_values: SpecialValues {
+		infinity: &["Infinity"],
+		nan: &["NaN"],
+		signaling_nan: &[],
+		nan_payload: false,
+		ignore_case: false
+	},
+	skip_whitespace: false,
+	leading_zeros: false,
+	exponent_backoff: false,
+	..ParseOptions::new()
+};
+
+/// Parses a JSON5 number from the start of a byte slice.
+///
+/// JSON5 extends the numbers of JSON with those of ECMAScript 5.1:
+///
+/// * The number may have a leading `+` as well as a `-`.
+/// * The integer part or the fraction may be left out, as in `.5` or
+///   `5.`, but not both.
+/// * `Infinity` and `NaN` are accepted in this case with an optional
+///   sign.
+/// * Hexadecimal integers like `0x1F` are accepted with an optional sign.
+///   Integers that don't fit in the 53 bits of an `f64` are correctly
+///   rounded.
+///
+/// Like in JSON, leading zeros and whitespace are not allowed, and the
+/// exponent marker must be followed by digits.
+///
+/// Returns the value and the length of the number in bytes. The number
+/// ends at the first byte that cannot continue it, and checking that byte
+/// is left to the tokenizer.
+///
+/// ```
+/// use strtod::json5::parse_number;
+///
+/// assert_eq!(parse_number(b"+.5,"), Ok((0.5, 3)));
+/// assert_eq!(parse_number(b"-0x1F}"), Ok((-31.0, 5)));
+/// assert_eq!(parse_number(b"0x20000000000001"), Ok((9007199254740992.0, 16)));
+/// ```
+pub fn parse_number(input: &[u8]) -> Result<(f64, usize), ParseError> {
+	let mut parser = Parser::new(&OPTIONS);
+	
+	let start = match input.first() {
+		Some(b'+') | Some(b'-') => 1,
+		_ => 0
+	};
+	
+	if input[start..].starts_with(b"0x") || input[start..].starts_with(b"0X") {
+		let s = Chars { bytes: input, offset: start + 2 };
+		
+		if !parser.integer(s, 16) {
+			return Err(ParseError::NoDigits(start + 2));
+		}
+		
+		let d = if input[0] == b'-' { -parser.rv.d } else { parser.rv.d };
+		return Ok((d, parser.end));
+	}
+	
+	let d = parser.parse(input)?;
+	
+	Ok((d, parser.end))
+}
+
+#[cfg(test)]
+mod test {
+	use super::parse_number;
+	use test::check;
+	use ParseError;
+	use std::f64;
+	
+	#[test]
+	pub fn decimal_tests() {
+		check(parse, "0", Ok((0.0, 1)));
+		check(parse, "-0", Ok((-0.0, 2)));
+		check(parse, "+1", Ok((1.0, 2)));
+		check(parse, "12.5", Ok((12.5, 4)));
+		check(parse, ".5", Ok((0.5, 2)));
+		check(parse, "-.5", Ok((-0.5, 3)));
+		check(parse, "5.", Ok((5.0, 2)));
+		check(parse, "+5.e3", Ok((5000.0, 5)));
+		check(parse, "0.1", Ok((0.1, 3)));
+		check(parse, "1E+2", Ok((100.0, 4)));
+		check(parse, "1e400", Ok((f64::INFINITY, 5)));
+		check(parse, "1.5,", Ok((1.5, 3)));
+		check(parse, "", Err(ParseError::Empty(0)));
+		check(parse, "+", Err(ParseError::SignWithoutDigits(1)));
+		check(parse, " 1", Err(ParseError::InvalidCharacter(0)));
+		check(parse, ".", Err(ParseError::NoDigits(0)));
+		check(parse, "01", Err(ParseError::LeadingZero(0)));
+		check(parse, "-007", Err(ParseError::LeadingZero(1)));
+		check(parse, "1e", Err(ParseError::DanglingExponent(1)));
+		check(parse, "1e+,", Err(ParseError::DanglingExponent(1)));
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		check(parse, "Infinity", Ok((f64::INFINITY, 8)));
+		check(parse, "+Infinity", Ok((f64::INFINITY, 9)));
+		check(parse, "-Infinity]", Ok((f64::NEG_INFINITY, 9)));
+		check(parse, "NaN", Ok((f64::NAN, 3)));
+		check(parse, "-NaN", Ok((-f64::NAN, 4)));
+		check(parse, "infinity", Err(ParseError::InvalidCharacter(0)));
+		check(parse, "nan", Err(ParseError::InvalidCharacter(0)));
+	}
+	
+	#[test]
+	pub fn hex_tests() {
+		check(parse, "0x1F", Ok((31.0, 4)));
+		check(parse, "0XfF", Ok((255.0, 4)));
+		check(parse, "+0x10", Ok((16.0, 5)));
+		check(parse, "-0x10", Ok((-16.0, 5)));
+		check(parse, "-0x0", Ok((-0.0, 4)));
+		check(parse, "0x1.8p3", Ok((1.0, 3)));
+		check(parse, "0x1g", Ok((1.0, 3)));
+		check(parse, "0x", Err(ParseError::NoDigits(2)));
+		check(parse, "-0xg", Err(ParseError::NoDigits(3)));
+	}
+	
+	#[test]
+	pub fn hex_rounding_tests() {
+		// Hexadecimal integers are rounded once, from all of their digits.
+		check(parse, "0x0000000000000000000001", Ok((1.0, 24)));
+		check(parse, "0x1fffffffffffff", Ok((9007199254740991.0, 16)));
+		check(parse, "0x20000000000001", Ok((9007199254740992.0, 16)));
+		check(parse, "0x20000000000003", Ok((9007199254740996.0, 16)));
+		check(parse, "0x20000000000001000000000000000000001", Ok((1.7422457186352053e+41, 37)));
+		check(parse, "0xffffffffffffffffffffffffffffffff", Ok((3.402823669209385e+38, 34)));
+	}
+	
+	fn parse(input: &str) -> Result<(f64, usize), ParseError> {
+		parse_number(input.as_bytes())
+	}
+}
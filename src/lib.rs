@@ -4,9 +4,23 @@
 #![allow(non_upper_case_globals)]
 #![allow(non_snake_case)]
 
+use std::error;
+use std::fmt;
 use std::mem::transmute;
 use std::num::Wrapping;
 
+pub mod ecmascript;
+pub mod from_chars;
+pub mod glibc;
+pub mod go;
+pub mod java;
+pub mod json;
+pub mod json5;
+pub mod python;
+pub mod rust;
+pub mod toml;
+pub mod yaml;
+
 const DBL_DIG : u32 = 15;
 const DBL_MAX_10_EXP : i32 = 308;
 const DBL_MAX_EXP : u32 = 1024;
@@ -16,7 +30,7 @@ const Exp_msk1 : u32 = 0x100000;
 const Exp_mask : u32 = 0x7ff00000;
 const P : u32 = 53;
 const Bias : i32 = 1023;
-const Emin : i32 = (-1022);
+const Emin : i32 = -1022;
 const Exp_1 : u32 = 0x3ff00000;
 const Ebits : u32 = 11;
 const Frac_mask : u32 = 0xfffff;
@@ -28,11 +42,20 @@ const LSB : u32 = 1;
 const Log2P : i32 = 1;
 const Tiny1 : u32 = 1;
 const Flt_Rounds : u32 = 1;
+#[allow(clippy::precedence)]
 const Big0 : u32 = Frac_mask1 | Exp_msk1 * (DBL_MAX_EXP + Bias as u32 - 1);
 const Big1 : u32 = 0xffffffff;
 const n_bigtens : u32 = 5;
 const Scale_Bit : i32 = 0x10;
 
+const NAN_WORD0 : u32 = 0x7ff80000;
+const NAN_WORD1 : u32 = 0;
+const NAN_QUIET : u32 = 0x80000;
+
+const Single_P : u32 = 24;
+const Single_Bias : i32 = 127;
+const Single_Emin : i32 = -126;
+
 const CTAB : u32 = 9;
 const CNL : u32 = 10;
 const CVT : u32 = 11;
@@ -41,11 +64,14 @@ const CCR : u32 = 13;
 const CSP : u32 = 32;
 const CPLUS : u32 = 43;
 const CMIN : u32 = 45;
-const CDOT : u32 = 46;
 const C0 : u32 = 48;
 const C9 : u32 = 57;
-const CE : u32 = 69;
-const Ce : u32 = 101;
+const CLPAR : u32 = 40;
+const CRPAR : u32 = 41;
+const CP : u32 = 80;
+const Cp : u32 = 112;
+const CX : u32 = 88;
+const Cx : u32 = 120;
 
 static tens : [f64; 23] = [
 	1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9,
@@ -68,12 +94,665 @@ static tinytens : [f64; 5] = [
 /// This implementation is a translation from
 /// http://mxr.mozilla.org/mozilla-central/source/js/src/dtoa.c.
 pub fn strtod(input: &str) -> Option<f64> {
-	let mut parser = Parser {
-		sign: false,
-		rv: U { d: 0_f64 }
+	strtod_bytes(input.as_bytes())
+}
+
+/// Parses a 64-bit floating point number from a byte slice.
+///
+/// The input does not have to be valid UTF-8. See `strtod` for details.
+pub fn strtod_bytes(input: &[u8]) -> Option<f64> {
+	let mut parser = Parser::new(&DEFAULT_OPTIONS);
+	
+	lenient(parser.parse(input))
+}
+
+/// Parses a 32-bit floating point number.
+///
+/// The input is parsed like with `strtod`, but the result is rounded
+/// directly to single precision. This is not the same as
+/// `strtod(input).map(|d| d as f32)`, which rounds twice and can be off by
+/// one in the last place near halfway points.
+///
+/// The digits are converted to an approximation that is then corrected in
+/// single precision: it is compared with the exact decimal value and
+/// stepped to the neighbouring `f32` until it is the closest one.
+pub fn strtof(input: &str) -> Option<f32> {
+	let mut parser = Parser::new(&DEFAULT_OPTIONS);
+	parser.single = true;
+	
+	lenient(parser.parse(input.as_bytes())).map(narrow)
+}
+
+// Kept for compatibility; `"."` and `"e5"` parse as zero.
+fn lenient(result: Result<f64, ParseError>) -> Option<f64> {
+	match result {
+		Ok(d) => Some(d),
+		Err(ParseError::NoDigits(_)) => Some(0_f64),
+		Err(_) => None
+	}
+}
+
+/// Parses a 64-bit floating point number, reporting why parsing failed.
+///
+/// Leading whitespace and trailing characters are ignored, like with
+/// `strtod`. Unlike `strtod`, inputs without any digits such as `"."` or
+/// `"e5"` are rejected.
+pub fn try_strtod(input: &str) -> Result<f64, ParseError> {
+	try_strtod_bytes(input.as_bytes())
+}
+
+/// Parses a 64-bit floating point number from a byte slice, reporting why
+/// parsing failed.
+///
+/// See `try_strtod` for details.
+pub fn try_strtod_bytes(input: &[u8]) -> Result<f64, ParseError> {
+	let mut parser = Parser::new(&DEFAULT_OPTIONS);
+	
+	parser.parse(input)
+}
+
+/// Parses a 64-bit floating point number from the start of a string.
+///
+/// This follows the `endptr` contract of C `strtod`: next to the parsed
+/// value, the number of bytes that make up the number (including leading
+/// whitespace and the sign) is returned. Trailing characters are not
+/// consumed, so e.g. for `"1e+"` only the `"1"` is consumed.
+///
+/// Unlike `strtod`, `None` is returned when no number could be recognized
+/// at all, e.g. for `"."` or `"e5"`.
+pub fn strtod_prefix(input: &str) -> Option<(f64, usize)> {
+	strtod_bytes_prefix(input.as_bytes())
+}
+
+/// Parses a 64-bit floating point number from the start of a byte slice.
+///
+/// See `strtod_prefix` for details.
+pub fn strtod_bytes_prefix(input: &[u8]) -> Option<(f64, usize)> {
+	let mut parser = Parser::new(&DEFAULT_OPTIONS);
+	
+	match parser.parse(input) {
+		Ok(d) => Some((d, parser.end)),
+		Err(_) => None
+	}
+}
+
+/// Parses a 64-bit floating point number that must span the whole input.
+///
+/// Leading whitespace is skipped. Trailing whitespace is only accepted when
+/// `trailing_whitespace` is set; any other trailing character is rejected,
+/// and so are inputs without digits like `"."`. Where `strtod` stops at
+/// `"1e+"` or at the `,` in `"0,5"`, this function returns an error.
+pub fn strtod_strict(input: &str, trailing_whitespace: bool) -> Result<f64, ParseError> {
+	strtod_bytes_strict(input.as_bytes(), trailing_whitespace)
+}
+
+/// Parses a 64-bit floating point number that must span the whole byte
+/// slice.
+///
+/// See `strtod_strict` for details.
+pub fn strtod_bytes_strict(input: &[u8], trailing_whitespace: bool) -> Result<f64, ParseError> {
+	let mut parser = Parser::new(&DEFAULT_OPTIONS);
+	
+	parser.parse_strict(input, trailing_whitespace)
+}
+
+/// Parses a 64-bit floating point number using the grammar described by
+/// `options`.
+///
+/// Leading whitespace and trailing characters are ignored. With default
+/// options this is the same as `try_strtod`.
+pub fn strtod_with(input: &str, options: &ParseOptions) -> Result<f64, ParseError> {
+	strtod_bytes_with(input.as_bytes(), options)
+}
+
+/// Parses a 64-bit floating point number from a byte slice using the
+/// grammar described by `options`.
+///
+/// See `strtod_with` for details.
+pub fn strtod_bytes_with(input: &[u8], options: &ParseOptions) -> Result<f64, ParseError> {
+	let mut parser = Parser::new(options);
+	
+	parser.parse(input)
+}
+
+/// Parses a 64-bit floating point number from the start of a string using
+/// the grammar described by `options`.
+///
+/// Returns the value and the number of bytes that make up the number, like
+/// `strtod_prefix`.
+///
+/// ```
+/// use strtod::{strtod_prefix_with, ParseOptions};
+///
+/// let options = ParseOptions::new().decimal_point(',');
+///
+/// assert_eq!(strtod_prefix_with("2,5;", &options), Ok((2.5, 3)));
+/// ```
+pub fn strtod_prefix_with(input: &str, options: &ParseOptions) -> Result<(f64, usize), ParseError> {
+	strtod_bytes_prefix_with(input.as_bytes(), options)
+}
+
+/// Parses a 64-bit floating point number from the start of a byte slice
+/// using the grammar described by `options`.
+///
+/// See `strtod_prefix_with` for details.
+pub fn strtod_bytes_prefix_with(input: &[u8], options: &ParseOptions) -> Result<(f64, usize), ParseError> {
+	let mut parser = Parser::new(options);
+	let d = parser.parse(input)?;
+	
+	Ok((d, parser.end))
+}
+
+/// Parses a 64-bit floating point number like `strtod_with`, and reports
+/// how the result relates to the exact decimal value.
+///
+/// ```
+/// use strtod::{strtod_conversion, Exactness, ParseOptions};
+///
+/// let conversion = strtod_conversion("0.1", &ParseOptions::new()).unwrap();
+///
+/// assert_eq!(conversion.value, 0.1);
+/// assert_eq!(conversion.exactness, Exactness::RoundedUp);
+/// ```
+pub fn strtod_conversion(input: &str, options: &ParseOptions) -> Result<Conversion, ParseError> {
+	let mut parser = Parser::new(options);
+	
+	parser.convert(input.as_bytes())
+}
+
+/// Parses a 32-bit floating point number using the grammar described by
+/// `options`.
+///
+/// The result is rounded directly to single precision, like with `strtof`.
+///
+/// ```
+/// use strtod::{strtof_with, ParseOptions, RoundingMode};
+///
+/// let options = ParseOptions::new().rounding_mode(RoundingMode::TowardZero);
+///
+/// assert_eq!(strtof_with("0.1", &options), Ok(0.099999994));
+/// ```
+pub fn strtof_with(input: &str, options: &ParseOptions) -> Result<f32, ParseError> {
+	let mut parser = Parser::new(options);
+	parser.single = true;
+	
+	parser.parse(input.as_bytes()).map(narrow)
+}
+
+/// Parses a 32-bit floating point number like `strtof_with`, and reports
+/// how the result relates to the exact decimal value.
+pub fn strtof_conversion(input: &str, options: &ParseOptions) -> Result<Conversion<f32>, ParseError> {
+	let mut parser = Parser::new(options);
+	parser.single = true;
+	
+	let conversion = parser.convert(input.as_bytes())?;
+	
+	Ok(Conversion {
+		value: narrow(conversion.value),
+		exactness: conversion.exactness,
+		was_tie: conversion.was_tie,
+		range: conversion.range
+	})
+}
+
+/// The result of a conversion along with how it was rounded.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Conversion<T = f64> {
+	/// The parsed number.
+	pub value: T,
+	/// How `value` compares with the exact value of the input.
+	pub exactness: Exactness,
+	/// Whether the exact value lies halfway between two floating point
+	/// numbers. Ties are never exact.
+	pub was_tie: bool,
+	/// Whether the exact value was too large or too small for a normal
+	/// number.
+	pub range: Range
+}
+
+impl<T> Conversion<T> {
+	/// Returns whether `value` is the exact value of the input. This is
+	/// the negation of the IEEE 754 inexact flag.
+	pub fn is_exact(&self) -> bool {
+		self.exactness == Exactness::Exact
+	}
+}
+
+/// How a parsed number compares with the exact value of its input.
+///
+/// Infinities and NaNs that are spelled out are exact, while a number that
+/// overflows to infinity is rounded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Exactness {
+	/// The input is represented exactly.
+	Exact,
+	/// The result is greater than the exact value.
+	RoundedUp,
+	/// The result is less than the exact value.
+	RoundedDown
+}
+
+/// Where the exact value of a parsed number lies relative to the range of
+/// normal floating point numbers.
+///
+/// These are the cases in which C `strtod` sets `errno` to `ERANGE`,
+/// except that a subnormal result is reported even when it is exact.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Range {
+	/// The result is zero, a normal number, or an infinity or NaN that
+	/// was spelled out.
+	Normal,
+	/// The result is a non-zero subnormal number.
+	Subnormal,
+	/// The exact value is non-zero but the result is zero.
+	Underflow,
+	/// The exact value is too large for a finite number. The result is
+	/// then infinity or the largest finite number, depending on the
+	/// rounding mode and the `Overflow` option.
+	Overflow
+}
+
+/// What to do when the exact value is too large for a finite number.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Overflow {
+	/// Return the rounded result, which is infinity unless the rounding
+	/// mode rounds toward zero. This is what C `strtod` does.
+	#[default]
+	Infinity,
+	/// Return the largest finite number with the sign of the input.
+	Saturate,
+	/// Return `ParseError::OutOfRange`.
+	Error
+}
+
+/// The reason a floating point number could not be parsed.
+///
+/// Every variant carries the byte offset into the input at which the
+/// problem was detected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+	/// The input is empty or contains only whitespace. The offset is the
+	/// end of the input.
+	Empty(usize),
+	/// The number has no digits, e.g. `"."` or `"e5"`, or is missing the
+	/// digits on one side of the decimal point when the options require
+	/// them. The offset is where the digits were expected.
+	NoDigits(usize),
+	/// A sign is not followed by a number, e.g. `"+"` or `"-x"`. The
+	/// offset is just past the sign.
+	SignWithoutDigits(usize),
+	/// An exponent marker is not followed by digits, e.g. `"1e+"`. The
+	/// offset is that of the exponent marker.
+	DanglingExponent(usize),
+	/// A character that cannot appear at this position.
+	InvalidCharacter(usize),
+	/// A group separator in a position that is not allowed, like the
+	/// second one in `"1__000"`.
+	MisplacedSeparator(usize),
+	/// The number is too large for a finite number and the options ask
+	/// for an error. The offset is that of the first character after the
+	/// sign.
+	OutOfRange(usize),
+	/// The integer part starts with a zero that is followed by another
+	/// digit, and the options do not allow that. The offset is that of
+	/// the zero.
+	LeadingZero(usize),
+	/// The number has no exponent where the grammar requires one, like a
+	/// hexadecimal number without a binary exponent in Java, or a Rust
+	/// literal that would be an integer. The offset is where the exponent
+	/// was expected.
+	MissingExponent(usize)
+}
+
+impl ParseError {
+	/// Returns the byte offset at which the error was detected.
+	pub fn offset(&self) -> usize {
+		match *self {
+			ParseError::Empty(offset) |
+			ParseError::NoDigits(offset) |
+			ParseError::SignWithoutDigits(offset) |
+			ParseError::DanglingExponent(offset) |
+			ParseError::InvalidCharacter(offset) |
+			ParseError::MisplacedSeparator(offset) |
+			ParseError::OutOfRange(offset) |
+			ParseError::LeadingZero(offset) |
+			ParseError::MissingExponent(offset) => offset
+		}
+	}
+	
+	// The same error at another offset, for parsers that work on a copy of
+	// the input.
+	fn with_offset(self, offset: usize) -> ParseError {
+		match self {
+			ParseError::Empty(_) => ParseError::Empty(offset),
+			ParseError::NoDigits(_) => ParseError::NoDigits(offset),
+			ParseError::SignWithoutDigits(_) => ParseError::SignWithoutDigits(offset),
+			ParseError::DanglingExponent(_) => ParseError::DanglingExponent(offset),
+			ParseError::InvalidCharacter(_) => ParseError::InvalidCharacter(offset),
+			ParseError::MisplacedSeparator(_) => ParseError::MisplacedSeparator(offset),
+			ParseError::OutOfRange(_) => ParseError::OutOfRange(offset),
+			ParseError::LeadingZero(_) => ParseError::LeadingZero(offset),
+			ParseError::MissingExponent(_) => ParseError::MissingExponent(offset)
+		}
+	}
+}
+
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let message = match *self {
+			ParseError::Empty(_) => "empty input",
+			ParseError::NoDigits(_) => "no digits",
+			ParseError::SignWithoutDigits(_) => "sign without digits",
+			ParseError::DanglingExponent(_) => "exponent without digits",
+			ParseError::InvalidCharacter(_) => "invalid character",
+			ParseError::MisplacedSeparator(_) => "misplaced group separator",
+			ParseError::OutOfRange(_) => "number out of range",
+			ParseError::LeadingZero(_) => "leading zero",
+			ParseError::MissingExponent(_) => "missing exponent"
+		};
+		
+		write!(f, "{} at offset {}", message, self.offset())
+	}
+}
+
+impl error::Error for ParseError {}
+
+static DEFAULT_OPTIONS : ParseOptions = ParseOptions::new();
+
+/// Options that control the grammar accepted by `strtod_with`.
+///
+/// The default options accept the same input as `strtod`.
+#[derive(Clone, Debug)]
+pub struct ParseOptions {
+	special_values: SpecialValues,
+	hex_floats: bool,
+	decimal_point: char,
+	group_separators: GroupSeparators,
+	rounding_mode: RoundingMode,
+	overflow: Overflow,
+	skip_whitespace: bool,
+	leading_plus: bool,
+	leading_decimal_point: bool,
+	trailing_decimal_point: bool,
+	leading_zeros: bool,
+	exponent_backoff: bool,
+	exponent_markers: &'static [char]
+}
+
+impl ParseOptions {
+	/// Creates the default options.
+	pub const fn new() -> ParseOptions {
+		ParseOptions {
+			special_values: SpecialValues::NONE,
+			hex_floats: false,
+			decimal_point: '.',
+			group_separators: GroupSeparators::NONE,
+			rounding_mode: RoundingMode::NearestEven,
+			overflow: Overflow::Infinity,
+			skip_whitespace: true,
+			leading_plus: true,
+			leading_decimal_point: true,
+			trailing_decimal_point: true,
+			leading_zeros: true,
+			exponent_backoff: true,
+			exponent_markers: &['e', 'E']
+		}
+	}
+	
+	/// Sets whether leading ASCII whitespace is skipped. Otherwise it is
+	/// an invalid character. Defaults to `true`.
+	pub fn skip_whitespace(mut self, skip_whitespace: bool) -> ParseOptions {
+		self.skip_whitespace = skip_whitespace;
+		self
+	}
+	
+	/// Sets whether the number may start with a `+` sign. Otherwise the
+	/// `+` is an invalid character. Defaults to `true`.
+	pub fn leading_plus(mut self, leading_plus: bool) -> ParseOptions {
+		self.leading_plus = leading_plus;
+		self
+	}
+	
+	/// Sets whether the integer part may be left out, as in `.5`.
+	/// Otherwise the digits missing in front of the decimal point are
+	/// reported as `ParseError::NoDigits`. Defaults to `true`.
+	pub fn leading_decimal_point(mut self, leading_decimal_point: bool) -> ParseOptions {
+		self.leading_decimal_point = leading_decimal_point;
+		self
+	}
+	
+	/// Sets whether the fraction may be left out after the decimal point,
+	/// as in `5.`. Otherwise the digits missing after the decimal point
+	/// are reported as `ParseError::NoDigits`. Defaults to `true`.
+	pub fn trailing_decimal_point(mut self, trailing_decimal_point: bool) -> ParseOptions {
+		self.trailing_decimal_point = trailing_decimal_point;
+		self
+	}
+	
+	/// Sets whether the integer part may have leading zeros, as in `007`.
+	/// Otherwise they are reported as `ParseError::LeadingZero`. A single
+	/// zero in front of the decimal point or the exponent is always
+	/// allowed. Defaults to `true`.
+	pub fn leading_zeros(mut self, leading_zeros: bool) -> ParseOptions {
+		self.leading_zeros = leading_zeros;
+		self
+	}
+	
+	/// Sets whether an exponent marker without digits, like the `e` in
+	/// `1e+`, ends the number in front of it as in C. Otherwise it is
+	/// reported as `ParseError::DanglingExponent`. Defaults to `true`.
+	pub fn exponent_backoff(mut self, exponent_backoff: bool) -> ParseOptions {
+		self.exponent_backoff = exponent_backoff;
+		self
+	}
+	
+	/// Sets the characters that introduce the decimal exponent. Defaults
+	/// to `e` and `E`.
+	pub fn exponent_markers(mut self, exponent_markers: &'static [char]) -> ParseOptions {
+		self.exponent_markers = exponent_markers;
+		self
+	}
+	
+	/// Sets what happens when a number is too large for a finite number.
+	/// Defaults to `Overflow::Infinity`.
+	pub fn overflow(mut self, overflow: Overflow) -> ParseOptions {
+		self.overflow = overflow;
+		self
+	}
+	
+	/// Sets the direction in which a number that cannot be represented
+	/// exactly is rounded. Defaults to `RoundingMode::NearestEven`.
+	pub fn rounding_mode(mut self, rounding_mode: RoundingMode) -> ParseOptions {
+		self.rounding_mode = rounding_mode;
+		self
+	}
+	
+	/// Sets the character that separates the integer part from the
+	/// fraction, like `','` for `"3,14"`. Any character can be used,
+	/// including multi-byte ones like U+066B ARABIC DECIMAL SEPARATOR.
+	/// Defaults to `'.'`.
+	///
+	/// # Panics
+	///
+	/// Panics if `decimal_point` is an ASCII digit.
+	pub fn decimal_point(mut self, decimal_point: char) -> ParseOptions {
+		assert!(!decimal_point.is_ascii_digit(), "decimal point cannot be a digit");
+		self.decimal_point = decimal_point;
+		self
+	}
+	
+	/// Sets the characters that may separate groups of digits and where
+	/// they may appear. Defaults to `GroupSeparators::NONE`.
+	///
+	/// A separator that is also the decimal point is read as the decimal
+	/// point. A separator in a position that `group_separators` does not
+	/// allow is reported as `ParseError::MisplacedSeparator`.
+	///
+	/// # Panics
+	///
+	/// Panics if one of the separators is an ASCII digit.
+	pub fn group_separators(mut self, group_separators: GroupSeparators) -> ParseOptions {
+		assert!(!group_separators.characters.iter().any(char::is_ascii_digit),
+			"group separator cannot be a digit");
+		self.group_separators = group_separators;
+		self
+	}
+	
+	/// Sets whether C99 hexadecimal floating point numbers like `0x1.8p3`
+	/// are accepted. The binary exponent is optional. Defaults to `false`,
+	/// in which case `"0x1.8p3"` parses as `0`.
+	pub fn hex_floats(mut self, hex_floats: bool) -> ParseOptions {
+		self.hex_floats = hex_floats;
+		self
+	}
+	
+	/// Sets the spellings of infinity and NaN that are recognized after
+	/// the optional sign. Defaults to `SpecialValues::NONE`.
+	pub fn special_values(mut self, special_values: SpecialValues) -> ParseOptions {
+		self.special_values = special_values;
+		self
+	}
+}
+
+impl Default for ParseOptions {
+	fn default() -> ParseOptions {
+		ParseOptions::new()
+	}
+}
+
+/// The IEEE 754 rounding direction attributes.
+///
+/// The result is always the representable number in the given direction
+/// that is closest to the exact decimal value. Rounding toward zero or
+/// toward the opposite infinity turns an overflow into the largest finite
+/// number instead of infinity.
+///
+/// Only `NearestEven` is built into the conversion to `f64`. The other modes
+/// take the nearest-even result and make a second pass: the decimal input
+/// is converted to a big integer once more and compared with the
+/// neighbouring floating point numbers. So they are slower than the
+/// default, and so is `strtod_conversion`, which needs the same comparison
+/// to report the exactness.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+	/// Round to nearest, ties to even. This is the C default.
+	#[default]
+	NearestEven,
+	/// Round to nearest, ties away from zero.
+	NearestAway,
+	/// Round toward positive infinity.
+	TowardPositive,
+	/// Round toward negative infinity.
+	TowardNegative,
+	/// Round toward zero.
+	TowardZero
+}
+
+/// Spellings of infinity and NaN.
+///
+/// Signaling NaNs are not recognized by any of the predefined spellings;
+/// they can be added like this:
+///
+/// ```
+/// use strtod::SpecialValues;
+///
+/// let special_values = SpecialValues { signaling_nan: &["snan"], ..SpecialValues::C };
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SpecialValues {
+	/// Spellings of infinity. They are tried in order, so a spelling must
+	/// come before any of its prefixes.
+	pub infinity: &'static [&'static str],
+	/// Spellings of a quiet NaN, tried in order.
+	pub nan: &'static [&'static str],
+	/// Spellings of a signaling NaN, tried in order.
+	pub signaling_nan: &'static [&'static str],
+	/// Whether a NaN may be followed by a payload in parentheses, like
+	/// `nan(0x1234)`.
+	///
+	/// As with C `strtod`, the n-char-sequence between the parentheses is
+	/// read as an unsigned integer in the base given by its prefix (`0x`
+	/// for hexadecimal, `0` for octal, decimal otherwise) and stored in the
+	/// fraction bits below the quiet bit, truncated to fit. A sequence that
+	/// is not a number is consumed but yields the default NaN.
+	pub nan_payload: bool,
+	/// Whether the spellings are matched ignoring ASCII case.
+	pub ignore_case: bool
+}
+
+impl SpecialValues {
+	/// Infinity and NaN are not recognized.
+	pub const NONE : SpecialValues = SpecialValues {
+		infinity: &[],
+		nan: &[],
+		signaling_nan: &[],
+		nan_payload: false,
+		ignore_case: false
 	};
 	
-	parser.parse(input.as_bytes())
+	/// `inf`, `infinity`, `nan` and `nan(n-char-sequence)` in any case, as
+	/// recognized by C `strtod`.
+	pub const C : SpecialValues = SpecialValues {
+		infinity: &["infinity", "inf"],
+		nan: &["nan"],
+		signaling_nan: &[],
+		nan_payload: true,
+		ignore_case: true
+	};
+}
+
+/// Characters that separate groups of digits, like the underscores in
+/// `1_000_000` or the commas in `1,234,567.89`, and the positions in which
+/// they are allowed.
+///
+/// The rules apply to each run of digits on its own: the integer part, the
+/// fraction and the exponent. Thousands separators can be set up like
+/// this:
+///
+/// ```
+/// use strtod::{strtod_with, GroupSeparators, ParseOptions};
+///
+/// let options = ParseOptions::new().group_separators(GroupSeparators {
+///     characters: &[','],
+///     ..GroupSeparators::UNDERSCORE
+/// });
+///
+/// assert_eq!(strtod_with("1,234,567.89", &options), Ok(1234567.89));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GroupSeparators {
+	/// The separator characters.
+	pub characters: &'static [char],
+	/// Whether separators may come before the first digit of a run, like
+	/// `_1` or `1._5`.
+	pub leading: bool,
+	/// Whether separators may come after the last digit of a run, like
+	/// `1_` or `1_.5`.
+	pub trailing: bool,
+	/// Whether two or more separators may follow each other, like `1__0`.
+	pub consecutive: bool
+}
+
+impl GroupSeparators {
+	/// Digits cannot be grouped.
+	pub const NONE : GroupSeparators = GroupSeparators {
+		characters: &[],
+		leading: false,
+		trailing: false,
+		consecutive: false
+	};
+	
+	/// Single underscores between two digits, like `1_000.000_1`.
+	pub const UNDERSCORE : GroupSeparators = GroupSeparators {
+		characters: &['_'],
+		leading: false,
+		trailing: false,
+		consecutive: false
+	};
+}
+
+fn is_space(c: u32) -> bool {
+	matches!(c, CTAB | CNL | CVT | CFF | CCR | CSP)
 }
 
 #[derive(Copy, Clone)]
@@ -93,61 +772,198 @@ impl<'a> Chars<'a> {
 		}
 	}
 	
+	fn at_end(&self) -> bool {
+		self.offset == self.bytes.len()
+	}
+	
 	fn bump(&mut self) {
 		self.offset += 1;
 	}
+	
+	fn eat(&mut self, word: &str, ignore_case: bool) -> bool {
+		let word = word.as_bytes();
+		let rest = &self.bytes[self.offset..];
+		
+		if rest.len() < word.len() {
+			return false;
+		}
+		
+		let head = &rest[..word.len()];
+		if head == word || (ignore_case && head.eq_ignore_ascii_case(word)) {
+			self.offset += word.len();
+			true
+		} else {
+			false
+		}
+	}
 }
 
-struct Parser {
+struct Parser<'a> {
+	options: &'a ParseOptions,
 	sign: bool,
-	rv: U
+	rv: U,
+	end: usize,
+	single: bool,
+	assume_hex: bool,
+	conversion: bool,
+	exactness: Exactness,
+	tie: bool,
+	range: Range,
+	tiny: bool,
+	start: usize
 }
 
-impl Parser {
-	fn parse(&mut self, input: &[u8]) -> Option<f64> {
-		if !self.parse_impl(Chars { bytes: input, offset: 0 }) {
-			None
-		} else {
-			Some(if self.sign { -self.rv.d } else { self.rv.d })
+impl<'a> Parser<'a> {
+	fn new(options: &'a ParseOptions) -> Parser<'a> {
+		Parser {
+			options,
+			sign: false,
+			rv: U { d: 0_f64 },
+			end: 0,
+			single: false,
+			assume_hex: false,
+			conversion: false,
+			exactness: Exactness::Exact,
+			tie: false,
+			range: Range::Normal,
+			tiny: false,
+			start: 0
+		}
+	}
+	
+	fn parse(&mut self, input: &[u8]) -> Result<f64, ParseError> {
+		self.parse_impl(Chars { bytes: input, offset: 0 })?;
+		
+		if self.range == Range::Overflow {
+			match self.options.overflow {
+				Overflow::Infinity => {}
+				Overflow::Saturate => {
+					self.rv.d = if self.single { f32::MAX as f64 } else { f64::MAX };
+					self.exactness = Exactness::RoundedDown;
+				}
+				Overflow::Error => return Err(ParseError::OutOfRange(self.start))
+			}
+		}
+		
+		Ok(if self.sign { -self.rv.d } else { self.rv.d })
+	}
+	
+	fn convert(&mut self, input: &[u8]) -> Result<Conversion, ParseError> {
+		// The rounding is tracked for the magnitude, so the direction
+		// flips for negative numbers.
+		self.conversion = true;
+		let value = self.parse(input)?;
+		
+		let exactness = match self.exactness {
+			Exactness::RoundedUp if self.sign => Exactness::RoundedDown,
+			Exactness::RoundedDown if self.sign => Exactness::RoundedUp,
+			exactness => exactness
+		};
+		
+		Ok(Conversion { value, exactness, was_tie: self.tie, range: self.range })
+	}
+	
+	fn parse_strict(&mut self, input: &[u8], trailing_whitespace: bool) -> Result<f64, ParseError> {
+		let d = self.parse(input)?;
+		
+		let mut s = Chars { bytes: input, offset: self.end };
+		
+		if trailing_whitespace {
+			while s.offset < input.len() && is_space(s.peek()) {
+				s.bump();
+			}
 		}
+		
+		if s.offset < input.len() {
+			// The exponent marker is only left behind when its digits
+			// are missing.
+			let mut t = Chars { bytes: input, offset: self.end };
+			return Err(if self.exponent_marker(&mut t) {
+				ParseError::DanglingExponent(self.end)
+			} else {
+				ParseError::InvalidCharacter(s.offset)
+			});
+		}
+		
+		Ok(d)
 	}
 	
-	fn parse_impl(&mut self, mut s: Chars) -> bool {
+	#[allow(clippy::manual_range_contains)]
+	fn parse_impl(&mut self, mut s: Chars) -> Result<(), ParseError> {
 		let mut nz0 = 0;
 		let mut nz = 0;
+		let mut signed = false;
 		
 		loop {
+			if s.at_end() {
+				return Err(ParseError::Empty(s.offset));
+			}
+			
 			match s.peek() {
-				0 => return false,
 				CPLUS | CMIN => {
 					if s.peek() == CMIN {
 						self.sign = true;
+					} else if !self.options.leading_plus {
+						return Err(ParseError::InvalidCharacter(s.offset));
 					}
+					signed = true;
 					s.bump();
-					if s.peek() == 0 {
-						return false;
+					if s.at_end() {
+						return Err(ParseError::SignWithoutDigits(s.offset));
 					}
 					break;
 				}
-				CTAB | CNL | CVT | CFF | CCR | CSP => {},
+				c if is_space(c) && self.options.skip_whitespace => {},
 				_ => break
 			}
 			
 			s.bump();
 		}
 		
+		self.start = s.offset;
+		
+		if self.special(&mut s) {
+			self.end = s.offset;
+			return Ok(());
+		}
+		
+		if self.assume_hex {
+			// hexadecimal without the 0x prefix
+			return if self.hex(s)? { Ok(()) } else { Err(ParseError::NoDigits(s.offset)) };
+		}
+		
+		if self.options.hex_floats && s.peek() == C0 {
+			let mut t = s;
+			t.bump();
+			if t.peek() == Cx || t.peek() == CX {
+				t.bump();
+				if self.hex(t)? {
+					return Ok(());
+				}
+			}
+		}
+		
 		let start = s;
 		
+		self.separators(&mut s, false, 10)?;
+		
 		if s.peek() == C0 {
 			nz0 = 1;
 			
+			let zero = s.offset;
 			s.bump();
+			self.separators(&mut s, true, 10)?;
+			if !self.options.leading_zeros && s.peek() >= C0 && s.peek() <= C9 {
+				return Err(ParseError::LeadingZero(zero));
+			}
 			while s.peek() == C0 {
 				s.bump();
+				self.separators(&mut s, true, 10)?;
 			}
 			
-			if s.peek() == 0 {
-				return true;
+			if s.at_end() {
+				self.end = s.offset;
+				return Ok(());
 			}
 		}
 		
@@ -173,17 +989,26 @@ impl Parser {
 			
 			nd += 1;
 			s.bump();
+			self.separators(&mut s, true, 10)?;
 		}
 		
-		let mut nd0 = nd;
-		
-		if c == CDOT {
-			s.bump();
+		let dot = s.offset;
+		if self.decimal_point(&mut s) {
+			if nd == 0 && nz0 == 0 && !self.options.leading_decimal_point {
+				return Err(ParseError::NoDigits(dot));
+			}
+			
+			self.separators(&mut s, false, 10)?;
 			c = s.peek();
 			
+			if (c < C0 || c > C9) && !self.options.trailing_decimal_point {
+				return Err(ParseError::NoDigits(s.offset));
+			}
+			
 			if nd == 0 {
 				while c == C0 {
 					s.bump();
+					self.separators(&mut s, true, 10)?;
 					c = s.peek();
 					nz += 1;
 				}
@@ -219,20 +1044,19 @@ impl Parser {
 				}
 				
 				s.bump();
+				self.separators(&mut s, true, 10)?;
 				c = s.peek();
 			}
 		}
 		
 		let mut e = 0_i32;
-		if c == Ce || c == CE {
+		let s00 = s;
+		if self.exponent_marker(&mut s) {
 			if nd == 0 && nz == 0 && nz0 == 0 {
-				self.sign = false;
-				return true;
+				return Err(ParseError::NoDigits(start.offset));
 			}
 			
 			let mut esign = false;
-			
-			s.bump();
 			c = s.peek();
 			
 			match c {
@@ -241,32 +1065,40 @@ impl Parser {
 						esign = true;
 					}
 					s.bump();
-					c = s.peek();
 				}
 				_ => {}
 			}
 			
+			self.separators(&mut s, false, 10)?;
+			c = s.peek();
+			
 			if c >= C0 && c <= C9 {
 				while c == C0 {
 					s.bump();
+					self.separators(&mut s, true, 10)?;
 					c = s.peek();
 				}
 				
 				if c > C0 && c <= C9 {
 					let mut L = c - C0;
-					let s1 = s;
 					
 					s.bump();
+					self.separators(&mut s, true, 10)?;
 					c = s.peek();
 					
 					while c >= C0 && c <= C9 {
-						L = L * 10 + c - C0;
+						// Stop accumulating once L is known to be too
+						// large, so that it cannot overflow.
+						if L <= 19999 {
+							L = L * 10 + c - C0;
+						}
 						
 						s.bump();
+						self.separators(&mut s, true, 10)?;
 						c = s.peek();
 					}
 					
-					if s.offset - s1.offset > 8 || L > 19999 {
+					if L > 19999 {
 						// Avoid confusion from exponents
 					    // so large that e might overflow.
 					    
@@ -281,28 +1113,53 @@ impl Parser {
 				} else {
 					e = 0;
 				}
+			} else {
+				// No digits in the exponent; back off to before the
+				// exponent marker.
+				if !self.options.exponent_backoff {
+					return Err(ParseError::DanglingExponent(s00.offset));
+				}
+				s = s00;
 			}
 		}
 		
+		self.end = s.offset;
+		
 		if nd == 0 {
 			if nz == 0 && nz0 == 0 {
-				self.sign = false;
+				return Err(if s.offset > start.offset {
+					ParseError::NoDigits(start.offset)
+				} else if signed {
+					ParseError::SignWithoutDigits(start.offset)
+				} else {
+					ParseError::InvalidCharacter(start.offset)
+				});
 			}
-			return s.offset > start.offset;
+			return Ok(());
 		}
 		
 		e -= nf;
-		let mut e1 = e;
 		
-		// Now we have nd0 digits, starting at s0, followed by a
-	 	// decimal point, followed by nd-nd0 digits.  The number we're
+		// Now we have nd digits, starting at s0, possibly interrupted
+	 	// by the decimal point and group separators.  The number we're
 	 	// after is the integer represented by those digits times
 	 	// 10**e
 	 	
-	 	if nd0 == 0 {
-	 		nd0 = nd;
+	 	self.decimal(s0, nd, e, y, z);
+	 	
+	 	if self.single || self.conversion || self.options.rounding_mode != RoundingMode::NearestEven {
+	 		self.round(s0, nd, e, y);
 	 	}
 	 	
+	 	self.classify();
+	 	
+	 	Ok(())
+	}
+	
+	#[allow(clippy::collapsible_if, clippy::identity_op, clippy::manual_range_contains)]
+	fn decimal(&mut self, s0: Chars, nd: u32, mut e: i32, y: u32, z: u32) {
+		let mut e1 = e;
+		
 	 	let k = if nd < DBL_DIG + 1 { nd } else { DBL_DIG + 1 };
 	 	self.rv.d = y as f64;
 	 	if k > 9 {
@@ -310,12 +1167,12 @@ impl Parser {
 	 	}
 	 	if nd <= DBL_DIG && Flt_Rounds == 1 {
 	 		if e == 0 {
-	 			return true;
+	 			return;
 	 		}
 	 		if e > 0 {
 	 			if e <= Ten_pmax {
 	 				self.rv.d *= tens[e as usize];
-	 				return true;
+	 				return;
 	 			}
 	 			
 	 			let i = DBL_DIG - nd;
@@ -325,11 +1182,11 @@ impl Parser {
 				 	e -= i as i32;
 				 	self.rv.d *= tens[i as usize];
 				 	self.rv.d *= tens[e as usize];
-				 	return true;
+				 	return;
 	 			}
 	 		} else if e >= -Ten_pmax {
 	 			self.rv.d /= tens[-e as usize];
-	 			return true;
+	 			return;
 	 		}
 	 	}
 	 	
@@ -348,7 +1205,7 @@ impl Parser {
 	 		if e1 != 0 {
 	 			if e1 > DBL_MAX_10_EXP {
 	 				self.overflow();
-	 				return true;
+	 				return;
 	 			}
 	 			
 	 			e1 >>= 4;
@@ -372,7 +1229,7 @@ impl Parser {
 	 			let z = self.rv.word0() & Exp_mask;
 	 			if z > Exp_msk1 * (DBL_MAX_EXP + Bias as u32 - P) {
 	 				self.overflow();
-	 				return true;
+	 				return;
 	 			}
 	 			
 	 			if z > Exp_msk1 * (DBL_MAX_EXP + Bias as u32 - 1 - P) {
@@ -393,7 +1250,7 @@ impl Parser {
 	 		if e1 != 0 {
 	 			if e1 >= 1 << n_bigtens {
 	 				self.rv.d = 0_f64;
-	 				return true;
+	 				return;
 	 			}
 	 			
 	 			if e1 & Scale_Bit != 0 {
@@ -428,18 +1285,25 @@ impl Parser {
 	 					}
 	 					
 	 					if self.rv.d == 0_f64 {
-	 						return true;
+	 						return;
 	 					}
 	 				}
 	 			}
 	 		}
 	 	}
 	 	
+	 	if self.single {
+	 		// A few ulps of a double are well within one of a single;
+	 		// round() corrects it with the single precision parameters.
+	 		self.unscale(scale);
+	 		return;
+	 	}
+	 	
 	 	// Now the hard part -- adjusting rv to the correct value.
 	 	
 	 	// Put digits into bd: true value = bd * 10^e
 	 	
-	 	let bd0 = s2b(s0, nd0, nd, y);
+	 	let bd0 = s2b(s0, nd, y);
 	 	
 	 	loop {
 	 		let mut bd = bd0.clone();
@@ -523,7 +1387,7 @@ impl Parser {
  				delta = lshift(&delta, Log2P);
  				if cmp(&delta, &bs) > 0 {
  					if self.drop_down(scale) {
- 						return true;
+ 						return;
  					}
  				}
  				break;
@@ -551,21 +1415,38 @@ impl Parser {
 					}
 	 			} else if self.rv.word0() & Bndry_mask == 0 && self.rv.word1() == 0 {
 	 				if self.drop_down(scale) {
-	 					return true;
+	 					return;
 	 				}
 	 				break;
 	 			}
 	 			
-	 			if self.rv.word1() & LSB == 0 {
+	 			// When the result is denormal, its last bit is above
+	 			// that of the scaled rv.
+	 			let i = if scale != 0 {
+	 				2 * P as i32 + 1 - ((self.rv.word0() & Exp_mask) >> Exp_shift) as i32
+	 			} else {
+	 				0
+	 			};
+	 			let odd = if i <= 0 {
+	 				self.rv.word1() & LSB != 0
+	 			} else if i < 32 {
+	 				self.rv.word1() & LSB << i != 0
+	 			} else if i < 52 {
+	 				self.rv.word0() & LSB << (i - 32) != 0
+	 			} else {
+	 				true
+	 			};
+	 			
+	 			if !odd {
 	 				break;
 	 			}
 	 			
 	 			if dsign {
-	 				self.rv.d += ulp(self.rv);
+	 				self.rv.d += sulp(self.rv, scale);
 	 			} else {
-	 				self.rv.d -= ulp(self.rv);
+	 				self.rv.d -= sulp(self.rv, scale);
 	 				if self.rv.d == 0_f64 {
-	 					return true;
+	 					return;
 	 				}
 	 			}
 	 			
@@ -580,8 +1461,8 @@ impl Parser {
 	 				aadj1.d = 1_f64;
 	 			} else if self.rv.word1() != 0 || self.rv.word0() & Bndry_mask != 0 {
 	 				if self.rv.word1() == Tiny1 && self.rv.word0() == 0 {
-	 					self.rv.d == 0_f64;
-	 					return true;
+	 					self.rv.d = 0_f64;
+	 					return;
 	 				}
 	 				
 	 				aadj = 1_f64;
@@ -618,7 +1499,7 @@ impl Parser {
 	 			if (self.rv.word0() & Exp_mask) >= Exp_msk1 * (DBL_MAX_EXP + Bias as u32 - P) {
 	 				if rv0.word0() == Big0 && rv0.word1() == Big1 {
 	 					self.overflow();
-	 					return true;
+	 					return;
 	 				}
 	 				self.rv.set_word0(Big0);
 	 				self.rv.set_word1(Big1);
@@ -632,7 +1513,7 @@ impl Parser {
 	 			if scale != 0 && y <= 2 * P * Exp_msk1 {
 	 				if aadj <= 0x7fffffff as f64 {
 	 					let mut z = aadj as u32;
-	 					if z <= 0 {
+	 					if z == 0 {
 	 						z = 1;
 	 					}
 	 					aadj = z as f64;
@@ -663,14 +1544,495 @@ impl Parser {
 	 		}
  		}
  		
- 		if scale != 0 {
- 			let mut rv0 = U { d: 0_f64 };
- 			rv0.set_word0(Exp_1 - 2 * P * Exp_msk1);
- 			rv0.set_word1(0);
- 			self.rv.d *= rv0.d;
- 		}
- 		
- 		true
+ 		self.unscale(scale);
+	}
+	
+	// Undoes the scaling by 2^(2P) that keeps a tiny rv away from the
+	// denormals while it is adjusted.
+	fn unscale(&mut self, scale: i32) {
+		if scale != 0 {
+			let mut rv0 = U { d: 0_f64 };
+			rv0.set_word0(Exp_1 - 2 * P * Exp_msk1);
+			rv0.set_word1(0);
+			self.rv.d *= rv0.d;
+		}
+	}
+	
+	fn hex(&mut self, mut s: Chars) -> Result<bool, ParseError> {
+		// The significand is collected in m, so that the number is
+		// m * 2^e. Once m is full, the remaining digits only matter for
+		// rounding and are folded into sticky.
+		
+		let mut m = 0_u64;
+		let mut e = 0_i64;
+		let mut sticky = false;
+		let mut nd = 0;
+		let mut dot = false;
+		
+		self.separators(&mut s, false, 16)?;
+		
+		loop {
+			let c = s.peek();
+			
+			if !dot && self.decimal_point(&mut s) {
+				dot = true;
+				self.separators(&mut s, false, 16)?;
+				continue;
+			}
+			
+			let d = match hex_digit(c) {
+				Some(d) => d,
+				None => break
+			};
+			
+			if m >> 60 == 0 {
+				m = m << 4 | d as u64;
+				if dot {
+					e -= 4;
+				}
+			} else {
+				sticky |= d != 0;
+				if !dot {
+					e += 4;
+				}
+			}
+			
+			nd += 1;
+			s.bump();
+			self.separators(&mut s, true, 16)?;
+		}
+		
+		if nd == 0 {
+			// "0x" or "0x." is parsed as the 0 in front of it.
+			return Ok(false);
+		}
+		
+		let c = s.peek();
+		if c == Cp || c == CP {
+			let s00 = s;
+			let mut esign = false;
+			
+			s.bump();
+			let mut c = s.peek();
+			
+			match c {
+				CPLUS | CMIN => {
+					esign = c == CMIN;
+					s.bump();
+				}
+				_ => {}
+			}
+			
+			self.separators(&mut s, false, 10)?;
+			c = s.peek();
+			
+			if (C0 ..= C9).contains(&c) {
+				let mut L = 0_i64;
+				
+				while (C0 ..= C9).contains(&c) {
+					// Saturate; anything this large over- or underflows.
+					if L < 1 << 40 {
+						L = L * 10 + (c - C0) as i64;
+					}
+					
+					s.bump();
+					self.separators(&mut s, true, 10)?;
+					c = s.peek();
+				}
+				
+				e += if esign { -L } else { L };
+			} else {
+				if !self.options.exponent_backoff {
+					return Err(ParseError::DanglingExponent(s00.offset));
+				}
+				s = s00;
+			}
+		}
+		
+		self.end = s.offset;
+		self.binary(m, e, sticky);
+		
+		Ok(true)
+	}
+	
+	// Reads an integer in a power of two radix, like the digits after the
+	// 0b, 0o or 0x of an integer literal. Returns false when there are no
+	// digits.
+	fn integer(&mut self, mut s: Chars, radix: u32) -> bool {
+		let bits = radix.trailing_zeros();
+		let start = s.offset;
+		
+		let mut m = 0_u64;
+		let mut e = 0_i64;
+		let mut sticky = false;
+		
+		while let Some(d) = char::from_u32(s.peek()).and_then(|c| c.to_digit(radix)) {
+			if m >> (64 - bits) == 0 {
+				m = m << bits | d as u64;
+			} else {
+				sticky |= d != 0;
+				e += bits as i64;
+			}
+		
+			s.bump();
+		}
+		
+		if s.offset == start {
+			return false;
+		}
+		
+		self.end = s.offset;
+		self.binary(m, e, sticky);
+		
+		true
+	}
+	
+	// Rounds m * 2^e to a double, or single when parsing single precision,
+	// in the rounding mode of the options. Sticky tells whether there are
+	// non-zero bits below m.
+	fn binary(&mut self, mut m: u64, e: i64, sticky: bool) {
+		let m0 = m;
+		
+		if m == 0 {
+			self.rv.d = 0_f64;
+			return;
+		}
+		
+		let (p, emin, emax) = if self.single {
+			(Single_P as i64, Single_Emin as i64, Single_Bias as i64)
+		} else {
+			(P as i64, Emin as i64, Bias as i64)
+		};
+		
+		// Exponent of the last bit that fits in the result.
+		let top = e + 64 - m.leading_zeros() as i64 - 1;
+		let mut k = if top > emin { top } else { emin } - (p - 1);
+		
+		if k > e {
+			// Beyond 65 the shifted out bits are always below half.
+			let shift = if k - e > 65 { 65 } else { k - e };
+			let x = m as u128;
+			m = (x >> shift) as u64;
+			let rest = x & ((1 << shift) - 1);
+			let half = 1_u128 << (shift - 1);
+			
+			let up = match self.directed() {
+				Some(up) => up && (rest != 0 || sticky),
+				None => rest > half || (rest == half && (sticky || m & 1 != 0 || self.away()))
+			};
+			
+			if rest != 0 || sticky {
+				self.exactness = if up { Exactness::RoundedUp } else { Exactness::RoundedDown };
+				self.tie = rest == half && !sticky;
+			}
+			
+			if up {
+				m += 1;
+				if m == 1 << p {
+					m >>= 1;
+					k += 1;
+				}
+			}
+		} else {
+			m <<= e - k;
+		}
+		
+		if k + p - 1 > emax {
+			// Halfway between the largest finite number and the next
+			// power of two is the only tie that overflows.
+			self.tie &= top == emax;
+			self.range = Range::Overflow;
+			
+			if self.directed() != Some(false) {
+				self.exactness = Exactness::RoundedUp;
+				self.overflow();
+				return;
+			}
+			
+			self.exactness = Exactness::RoundedDown;
+			m = (1 << p) - 1;
+			k = emax - (p - 1);
+		}
+		
+		self.rv.d = ldexp(m, k as i32);
+		self.classify();
+		
+		let bits = if self.single { (self.rv.d as f32).to_bits() as u64 } else { self.rv.d.to_bits() };
+		self.tiny = self.tiny(bits, |n, k| {
+			// Both are close to the smallest normal number, so they
+			// fit in 128 bits once aligned.
+			let k = k as i64;
+			let (x, y) = if e >= k {
+				((m0 as u128) << (e - k), n as u128)
+			} else {
+				(m0 as u128, (n as u128) << (k - e))
+			};
+			
+			if x != y {
+				if x < y { -1 } else { 1 }
+			} else {
+				sticky as i32
+			}
+		});
+	}
+	
+	fn decimal_point(&self, s: &mut Chars) -> bool {
+		s.eat(self.options.decimal_point.encode_utf8(&mut [0; 4]), false)
+	}
+	
+	fn exponent_marker(&self, s: &mut Chars) -> bool {
+		self.options.exponent_markers.iter().any(|c| s.eat(c.encode_utf8(&mut [0; 4]), false))
+	}
+	
+	// Skips the group separators at s. Digit tells whether they follow a
+	// digit of the current run, whose digits are in the given radix.
+	fn separators(&self, s: &mut Chars, digit: bool, radix: u32) -> Result<(), ParseError> {
+		let groups = self.options.group_separators;
+		let mut last = None;
+		
+		loop {
+			let offset = s.offset;
+			let found = groups.characters.iter().any(|&c| {
+				c != self.options.decimal_point && s.eat(c.encode_utf8(&mut [0; 4]), false)
+			});
+			
+			if !found {
+				break;
+			}
+			
+			if (last.is_some() && !groups.consecutive) || (!digit && !groups.leading) {
+				return Err(ParseError::MisplacedSeparator(offset));
+			}
+			
+			last = Some(offset);
+		}
+		
+		match last {
+			Some(offset) if !groups.trailing && (s.peek() as u8 as char).to_digit(radix).is_none() => {
+				Err(ParseError::MisplacedSeparator(offset))
+			}
+			_ => Ok(())
+		}
+	}
+	
+	fn special(&mut self, s: &mut Chars) -> bool {
+		let special = self.options.special_values;
+		
+		if special.infinity.iter().any(|word| s.eat(word, special.ignore_case)) {
+			self.rv.set_word0(Exp_mask);
+			self.rv.set_word1(0);
+			return true;
+		}
+		
+		let quiet = if special.nan.iter().any(|word| s.eat(word, special.ignore_case)) {
+			true
+		} else if special.signaling_nan.iter().any(|word| s.eat(word, special.ignore_case)) {
+			false
+		} else {
+			return false;
+		};
+		
+		let mut payload = 0;
+		
+		if special.nan_payload && s.peek() == CLPAR {
+			let mut t = *s;
+			t.bump();
+			let start = t.offset;
+			
+			while let b'0' ..= b'9' | b'a' ..= b'z' | b'A' ..= b'Z' | b'_' = t.peek() as u8 {
+				t.bump();
+			}
+			
+			if t.peek() == CRPAR {
+				payload = nan_payload(&t.bytes[start..t.offset]).unwrap_or(0);
+				t.bump();
+				*s = t;
+			}
+		}
+		
+		self.nan(quiet, payload);
+		
+		true
+	}
+	
+	fn nan(&mut self, quiet: bool, payload: u64) {
+		// The most significant fraction bit is the quiet bit, the payload
+		// goes in the bits below it. A signaling NaN needs a non-zero
+		// payload to not turn into infinity.
+		
+		let bits = if self.single { Single_P - 2 } else { P - 2 };
+		let mut payload = payload & ((1 << bits) - 1);
+		if !quiet && payload == 0 {
+			payload = 1;
+		}
+		
+		if self.single {
+			// Keep the payload in the bits that survive narrow().
+			payload <<= P - Single_P;
+		}
+		
+		let w = if quiet { NAN_WORD0 } else { NAN_WORD0 & !NAN_QUIET };
+		self.rv.set_word0(w | (payload >> 32) as u32);
+		self.rv.set_word1(NAN_WORD1 | payload as u32);
+	}
+	
+	fn round(&mut self, s0: Chars, nd: u32, e: i32, y: u32) {
+		// rv is the correctly rounded double, or for single precision
+		// an approximation within a few ulps of a double. The directed
+		// rounding modes may need the neighbour on the other side of the
+		// true value, and the approximation may be off by one in the
+		// last place of a single. So the approximation is stepped, in
+		// the current precision, until it and its successor bracket the
+		// true value, which then picks between the two according to the
+		// rounding mode.
+		
+		let (mut bits, inf) = if self.single {
+			((self.rv.d as f32).to_bits() as u64, f32::INFINITY.to_bits() as u64)
+		} else {
+			(self.rv.d.to_bits(), f64::INFINITY.to_bits())
+		};
+		
+		if self.single && self.directed().is_none() && self.rv.d == 0_f64 {
+			// Below half the smallest double, so far below half the
+			// smallest single. Infinity has no such margin: the doubles
+			// just below 2^128 - 2^103 still round to the largest single.
+			self.exactness = Exactness::RoundedDown;
+			self.tiny = true;
+			self.rv.d = 0_f64;
+			return;
+		}
+		
+		let bd = s2b(s0, nd, y);
+		let cmp_bits = |bits| {
+			let (m, k) = self.split(bits);
+			cmp_decimal(&bd, e, m, k)
+		};
+		
+		let mut i = cmp_bits(bits);
+		while i < 0 {
+			bits -= 1;
+			i = cmp_bits(bits);
+		}
+		while i > 0 && bits < inf {
+			let j = cmp_bits(bits + 1);
+			if j < 0 {
+				break;
+			}
+			bits += 1;
+			i = j;
+		}
+		
+		// Now bits <= the true value < bits + 1, unless bits is infinity.
+		let (bits, up) = if bits == inf {
+			// overflow
+			self.range = Range::Overflow;
+			if self.directed() == Some(false) { (inf - 1, Some(false)) } else { (inf, Some(true)) }
+		} else if i == 0 {
+			// exact result
+			(bits, None)
+		} else {
+			let (m, k) = self.split(bits);
+			let i = cmp_decimal(&bd, e, 2 * m + 1, k - 1);
+			self.tie = i == 0;
+			
+			let up = match self.directed() {
+				Some(up) => up,
+				None => i > 0 || (i == 0 && (m & 1 != 0 || self.away()))
+			};
+			
+			(if up { bits + 1 } else { bits }, Some(up))
+		};
+		
+		self.exactness = match up {
+			None => Exactness::Exact,
+			Some(true) => Exactness::RoundedUp,
+			Some(false) => Exactness::RoundedDown
+		};
+		
+		self.tiny = self.tiny(bits, |m, k| cmp_decimal(&bd, e, m, k));
+		
+		self.rv.d = if self.single {
+			f64::from(f32::from_bits(bits as u32))
+		} else {
+			f64::from_bits(bits)
+		};
+	}
+	
+	// Sets the range of a non-zero input from its result, unless an
+	// overflow to the largest finite number was found already.
+	fn classify(&mut self) {
+		let (min, max) = if self.single {
+			(f32::MIN_POSITIVE as f64, f32::MAX as f64)
+		} else {
+			(f64::MIN_POSITIVE, f64::MAX)
+		};
+		
+		let d = self.rv.d;
+		if self.range != Range::Overflow {
+			self.range = if d > max {
+				Range::Overflow
+			} else if d == 0_f64 {
+				Range::Underflow
+			} else if d < min {
+				Range::Subnormal
+			} else {
+				Range::Normal
+			};
+		}
+	}
+	
+	// Splits the bits of a non-negative number in the current precision
+	// into m * 2^k. Infinity is treated as the power of two following the
+	// largest finite number.
+	fn split(&self, bits: u64) -> (u64, i32) {
+		let (p, bias) = if self.single { (Single_P, Single_Bias) } else { (P, Bias) };
+		
+		let de = (bits >> (p - 1)) as i32;
+		let z = bits & ((1 << (p - 1)) - 1);
+		
+		if de != 0 {
+			(z | 1 << (p - 1), de - bias - (p - 1) as i32)
+		} else {
+			(z, 1 - bias - (p - 1) as i32)
+		}
+	}
+	
+	// Whether the result is tiny after rounding, the way glibc decides on
+	// ERANGE: rounded to full precision with an unbounded exponent, the
+	// magnitude is below the smallest normal number. This only differs
+	// from the result being subnormal when it was rounded up to the
+	// smallest normal number. Cmp compares the exact magnitude against
+	// m * 2^k.
+	fn tiny<F: Fn(u64, i32) -> i32>(&self, bits: u64, cmp: F) -> bool {
+		let min = 1 << if self.single { Single_P - 1 } else { P - 1 };
+		
+		if bits != min || self.exactness != Exactness::RoundedUp {
+			return bits < min;
+		}
+		
+		// The full precision neighbour below it and the halfway point
+		// between the two; ties go to the even smallest normal number.
+		let (m, k) = self.split(min);
+		match self.directed() {
+			Some(_) => cmp(2 * m - 1, k - 1) <= 0,
+			None => cmp(4 * m - 1, k - 2) < 0
+		}
+	}
+	
+	// Whether an inexact magnitude is rounded up in a directed rounding
+	// mode, or None when rounding to nearest.
+	fn directed(&self) -> Option<bool> {
+		match self.options.rounding_mode {
+			RoundingMode::NearestEven | RoundingMode::NearestAway => None,
+			RoundingMode::TowardPositive => Some(!self.sign),
+			RoundingMode::TowardNegative => Some(self.sign),
+			RoundingMode::TowardZero => Some(false)
+		}
+	}
+	
+	// Whether ties are rounded away from zero.
+	fn away(&self) -> bool {
+		self.options.rounding_mode == RoundingMode::NearestAway
 	}
 	
 	fn overflow(&mut self) {
@@ -682,7 +2044,7 @@ impl Parser {
 		// boundary case -- decrement exponent
 		if scale != 0 {
 			let L = self.rv.word0() & Exp_mask;
-			if L <= (2 * P + 1) & Exp_msk1 {
+			if L <= (2 * P + 1) * Exp_msk1 {
 				if L > (P + 2) * Exp_msk1 {
 					// round even ==>
 					// accept rv
@@ -710,7 +2072,7 @@ struct U {
 
 impl U {
 	fn word0(&self) -> u32 {
-		let words = unsafe { transmute::<_, [u32; 2]>(self.d) };
+		let words = unsafe { transmute::<f64, [u32; 2]>(self.d) };
 		
 		if cfg!(target_endian = "little") {
 			words[1]
@@ -720,7 +2082,7 @@ impl U {
 	}
 	
 	fn set_word0(&mut self, word: u32) {
-		let mut words = unsafe { transmute::<_, [u32; 2]>(self.d) };
+		let mut words = unsafe { transmute::<f64, [u32; 2]>(self.d) };
 		
 		if cfg!(target_endian = "little") {
 			words[1] = word;
@@ -728,11 +2090,11 @@ impl U {
 			words[0] = word;
 		}
 		
-		self.d = unsafe { transmute(words) };
+		self.d = unsafe { transmute::<[u32; 2], f64>(words) };
 	}
 	
 	fn word1(&self) -> u32 {
-		let words = unsafe { transmute::<_, [u32; 2]>(self.d) };
+		let words = unsafe { transmute::<f64, [u32; 2]>(self.d) };
 		
 		if cfg!(target_endian = "little") {
 			words[0]
@@ -742,7 +2104,7 @@ impl U {
 	}
 	
 	fn set_word1(&mut self, word: u32) {
-		let mut words = unsafe { transmute::<_, [u32; 2]>(self.d) };
+		let mut words = unsafe { transmute::<f64, [u32; 2]>(self.d) };
 		
 		if cfg!(target_endian = "little") {
 			words[0] = word;
@@ -750,7 +2112,7 @@ impl U {
 			words[1] = word;
 		}
 		
-		self.d = unsafe { transmute(words) };
+		self.d = unsafe { transmute::<[u32; 2], f64>(words) };
 	}
 }
 
@@ -779,6 +2141,31 @@ impl BigInt {
 	}
 }
 
+fn hex_digit(c: u32) -> Option<u32> {
+	(c as u8 as char).to_digit(16)
+}
+
+// Returns m * 2^k, which must be representable as a double.
+fn ldexp(m: u64, k: i32) -> f64 {
+	let mut a = U { d: 0_f64 };
+	
+	if k >= Emin {
+		a.set_word0(((k + Bias) as u32) << Exp_shift);
+		a.set_word1(0);
+	} else {
+		let j = k - Emin + Exp_shift as i32 + 32;
+		if j >= 32 {
+			a.set_word0(1 << (j - 32));
+			a.set_word1(0);
+		} else {
+			a.set_word0(0);
+			a.set_word1(1 << j);
+		}
+	}
+	
+	m as f64 * a.d
+}
+
 fn ulp(x: U) -> f64 {
 	let L = (x.word0() & Exp_mask) - (P - 1) * Exp_msk1;
 	let mut a = U { d: 0_f64 };
@@ -787,6 +2174,22 @@ fn ulp(x: U) -> f64 {
 	a.d
 }
 
+// The ulp of x, or of the final result when x is scaled and that result
+// is denormal.
+fn sulp(x: U, scale: i32) -> f64 {
+	let rv = ulp(x);
+	
+	let i = 2 * P as i32 + 1 - ((x.word0() & Exp_mask) >> Exp_shift) as i32;
+	if scale == 0 || i <= 0 {
+		return rv;
+	}
+	
+	let mut u = U { d: 0_f64 };
+	u.set_word0(Exp_1 + ((i as u32) << Exp_shift));
+	u.set_word1(0);
+	rv * u.d
+}
+
 fn ratio(a: &BigInt, b: &BigInt) -> f64 {
 	let (da, ka) = b2d(a);
 	let mut da = U { d: da };
@@ -853,6 +2256,7 @@ fn b2d(a: &BigInt) -> (f64, i32) {
 	(d.d, e)
 }
 
+#[allow(clippy::needless_return)]
 fn hi0bits(mut x: u32) -> u32 {
 	let mut k = 0;
 
@@ -881,42 +2285,34 @@ fn hi0bits(mut x: u32) -> u32 {
 	return k;
 }
 
-fn s2b(mut s: Chars, nd0: u32, nd: u32, y9: u32) -> BigInt {
+#[allow(clippy::manual_range_contains)]
+fn s2b(mut s: Chars, nd: u32, y9: u32) -> BigInt {
 	let mut b = BigInt::new();
 	b.x.push(y9);
-
-	let mut i = 9;
-	if 9 < nd0 {
-		s.offset += 9;
-		
-		loop {
-			let c = s.peek();
-			s.bump();
-			
-			multadd(&mut b, 10, c - C0);
-			
-			i += 1;
-			if i >= nd0 {
-				break;
-			}
-		}
-		
-		s.bump();
-	} else {
-		s.offset += 10;
-	}
 	
+	// The first nine digits are already in y9. Anything between the
+	// digits is the decimal point or a group separator, neither of which
+	// can contain an ASCII digit.
+	
+	let mut i = 0;
 	while i < nd {
 		let c = s.peek();
 		s.bump();
 		
-		multadd(&mut b, 10, c - C0);
+		if c < C0 || c > C9 {
+			continue;
+		}
+		
+		if i >= 9 {
+			multadd(&mut b, 10, c - C0);
+		}
 		i += 1;
 	}
 	
 	b
 }
 
+#[allow(clippy::identity_op, clippy::unnecessary_cast)]
 fn multadd(b: &mut BigInt, m: i32, a: u32) { // multiply by m and add a
 	let mut carry = a;
 	
@@ -976,6 +2372,43 @@ fn d2b(mut d: U) -> (BigInt, i32, u32) {
 	(b, e, bits)
 }
 
+// Reads a NaN payload like C strtoull with base 0.
+fn nan_payload(seq: &[u8]) -> Option<u64> {
+	let (digits, radix) = if seq.len() > 2 && seq[0] == b'0' && (seq[1] == b'x' || seq[1] == b'X') {
+		(&seq[2..], 16)
+	} else if !seq.is_empty() && seq[0] == b'0' {
+		(seq, 8)
+	} else {
+		(seq, 10)
+	};
+	
+	let mut payload = 0_u64;
+	
+	for &c in digits {
+		let digit = (c as char).to_digit(radix)?;
+		payload = payload
+			.checked_mul(radix as u64)
+			.and_then(|payload| payload.checked_add(digit as u64))
+			.unwrap_or(u64::MAX);
+	}
+	
+	Some(payload)
+}
+
+// Converts a double holding a single precision value to f32. Unlike a
+// cast, this keeps NaN payloads and signaling NaNs intact.
+fn narrow(d: f64) -> f32 {
+	if !d.is_nan() {
+		return d as f32;
+	}
+	
+	let u = U { d };
+	let sign = u.word0() & 0x80000000;
+	let fraction = ((u.word0() & Frac_mask) as u64) << 32 | u.word1() as u64;
+	
+	f32::from_bits(sign | 0x7f800000 | (fraction >> (P - Single_P)) as u32)
+}
+
 fn lo0bits(y: &mut u32) -> u32 {
 	let mut x = *y;
 
@@ -1056,6 +2489,7 @@ fn pow5mult(mut b: BigInt, mut k: i32) -> BigInt {
 	b
 }
 
+#[allow(clippy::identity_op, clippy::manual_swap)]
 fn mult<'a>(mut a: &'a BigInt, mut b: &'a BigInt) -> BigInt {
 	if a.x.len() < b.x.len() {
 		let c = a;
@@ -1136,6 +2570,7 @@ fn lshift(b: &BigInt, k: i32) -> BigInt {
 	b1
 }
 
+#[allow(clippy::identity_op, clippy::manual_swap)]
 fn diff<'a>(mut a: &'a BigInt, mut b: &'a BigInt) -> BigInt {
 	let mut i = cmp(a, b);
 	if i == 0 {
@@ -1194,12 +2629,60 @@ fn cmp<'a>(a: &'a BigInt, b: &'a BigInt) -> i32 {
 	0
 }
 
+// Compares bd * 10^e with m * 2^k.
+fn cmp_decimal(bd: &BigInt, e: i32, m: u64, k: i32) -> i32 {
+	let mut bd = bd.clone();
+	let mut bb = i2b(m as u32);
+	if m >> 32 != 0 {
+		bb.x.push((m >> 32) as u32);
+	}
+	
+	let (mut bd2, bd5, mut bb2, bb5) = if e >= 0 {
+		(e, e, 0_i32, 0_i32)
+	} else {
+		(0_i32, 0_i32, -e, -e)
+	};
+	
+	if k >= 0 {
+		bb2 += k;
+	} else {
+		bd2 -= k;
+	}
+	
+	let i = if bb2 < bd2 { bb2 } else { bd2 };
+	bb2 -= i;
+	bd2 -= i;
+	
+	if bd5 > 0 {
+		bd = pow5mult(bd, bd5);
+	}
+	if bb5 > 0 {
+		bb = pow5mult(bb, bb5);
+	}
+	if bd2 > 0 {
+		bd = lshift(&bd, bd2);
+	}
+	if bb2 > 0 {
+		bb = lshift(&bb, bb2);
+	}
+	
+	cmp(&bd, &bb)
+}
+
 #[cfg(test)]
 mod test {
 	#![allow(overflowing_literals)]
+	#![allow(clippy::excessive_precision)]
+	#![allow(clippy::octal_escapes)]
+	#![allow(clippy::unnecessary_unwrap)]
 	
-	use super::strtod;
+	use super::{strtod, strtod_prefix, try_strtod, strtod_strict, strtof, ParseError};
+	use super::{strtod_with, ParseOptions, SpecialValues, GroupSeparators, RoundingMode, Parser};
+	use super::{strtod_conversion, Conversion, Exactness, Overflow, Range};
+	use super::{strtod_bytes, strtod_bytes_prefix, try_strtod_bytes, strtod_bytes_strict};
+	use super::{strtod_bytes_with, strtod_prefix_with, strtod_bytes_prefix_with, strtof_with, strtof_conversion};
 	use std::f64;
+	use std::fmt::Debug;
 	
 	#[test]
 	pub fn tests() {
@@ -1467,6 +2950,587 @@ mod test {
 		test("1e309", Some(f64::INFINITY));
 	}
 	
+	#[test]
+	pub fn subnormal_tests() {
+		// 2^-1075, halfway between zero and the smallest denormal. The
+		// scaled correction loop lands on that denormal, which is a power
+		// of two, and must round down to the even zero.
+		let half = "2.470328229206232720882843964341106861825299013071623822127928412503377536351043\
+			75932649918180817996189898282347722858865463328355177969898199387398005390939063\
+			15035659515570226392290858392449105184435931802849936536152500319370457678249219\
+			36562366986365848075700158576926990370631192827955855133292783433840935197801553\
+			12465972635795746227664652728272200563740064854999770965994704540208281662262378\
+			57393450736339007967761930577506740176324673600968951340535537458516661134223766\
+			67860416215968046191446729184030053005753084904876539171138659164623952491262365\
+			38818796362393732804238910186723484976682350898633885879256283027559956575244555\
+			07255189313690836254779186948667994968324049705821028513185451396213837722826145\
+			437693412532098591327667236328125e-324";
+		test(half, Some(0.0));
+		
+		// 3 * 2^-1075 lies halfway between the two smallest denormals.
+		// The even one is above it, and the parity has to be taken from
+		// the last bit of the denormal, not of the scaled rv.
+		let three_halves = "7.410984687618698162648531893023320585475897039214871466383785237510132609053131\
+			27797949754542453988569694847043168576596389985065533909694598162194016172817189\
+			45106978546710679176872575177347315553307795408549809608457500958111373034747658\
+			09687100959097544227100475730780971111893578483867565399878350301522805593404659\
+			37397917907387238682993958184816601691220194564999312897984113620624844986787135\
+			72180352209017023903285791732520220528974020802906854021606612375549983402671300\
+			03581248647904138574340187552090159017259254714629617513415977493871857473787096\
+			16456389087181198412716730560170454930047052695901657637768849082679869725733665\
+			21765567941072508764337560846003984904972149117463085539556354188641513168478436\
+			313080237596295773983001708984375e-324";
+		test(three_halves, Some(f64::from_bits(2)));
+	}
+	
+	#[test]
+	pub fn prefix_tests() {
+		test_prefix("12.345", Some((12.345, 6)));
+		test_prefix("  -1.5xyz", Some((-1.5, 6)));
+		test_prefix("4\00012", Some((4.0, 1)));
+		test_prefix("1e5", Some((1e5, 3)));
+		test_prefix("1e", Some((1.0, 1)));
+		test_prefix("1e+", Some((1.0, 1)));
+		test_prefix("1e-x", Some((1.0, 1)));
+		test_prefix("1.e", Some((1.0, 2)));
+		test_prefix("000", Some((0.0, 3)));
+		test_prefix("-0,5", Some((-0.0, 2)));
+		test_prefix("0e+", Some((0.0, 1)));
+		test_prefix("1e309", Some((f64::INFINITY, 5)));
+		test_prefix("", None);
+		test_prefix("  ", None);
+		test_prefix("-", None);
+		test_prefix(".", None);
+		test_prefix(".y", None);
+		test_prefix("e5", None);
+		test_prefix("Inf", None);
+	}
+	
+	#[test]
+	pub fn error_tests() {
+		assert_eq!(try_strtod("12.5e1"), Ok(125.0));
+		assert_eq!(try_strtod("1e+"), Ok(1.0));
+		assert_eq!(try_strtod("000,,,e1"), Ok(0.0));
+		assert_eq!(try_strtod(""), Err(ParseError::Empty(0)));
+		assert_eq!(try_strtod(" \t"), Err(ParseError::Empty(2)));
+		assert_eq!(try_strtod("+"), Err(ParseError::SignWithoutDigits(1)));
+		assert_eq!(try_strtod(" -x"), Err(ParseError::SignWithoutDigits(2)));
+		assert_eq!(try_strtod("."), Err(ParseError::NoDigits(0)));
+		assert_eq!(try_strtod("-.y"), Err(ParseError::NoDigits(1)));
+		assert_eq!(try_strtod("e5"), Err(ParseError::NoDigits(0)));
+		assert_eq!(try_strtod("Inf"), Err(ParseError::InvalidCharacter(0)));
+		assert_eq!(ParseError::NoDigits(3).to_string(), "no digits at offset 3");
+	}
+	
+	#[test]
+	pub fn strict_tests() {
+		assert_eq!(strtod_strict("12.5", false), Ok(12.5));
+		assert_eq!(strtod_strict("  -1e3", false), Ok(-1e3));
+		assert_eq!(strtod_strict("5.", false), Ok(5.0));
+		assert_eq!(strtod_strict("12abc", false), Err(ParseError::InvalidCharacter(2)));
+		assert_eq!(strtod_strict("4\00012", false), Err(ParseError::InvalidCharacter(1)));
+		assert_eq!(strtod_strict("0,5", false), Err(ParseError::InvalidCharacter(1)));
+		assert_eq!(strtod_strict("000,,,e1", false), Err(ParseError::InvalidCharacter(3)));
+		assert_eq!(strtod_strict(".", false), Err(ParseError::NoDigits(0)));
+		assert_eq!(strtod_strict(".y", false), Err(ParseError::NoDigits(0)));
+		assert_eq!(strtod_strict("1e+", false), Err(ParseError::DanglingExponent(1)));
+		assert_eq!(strtod_strict("1.5E", false), Err(ParseError::DanglingExponent(3)));
+		assert_eq!(strtod_strict("1.5 ", false), Err(ParseError::InvalidCharacter(3)));
+		assert_eq!(strtod_strict("1.5 \r\n", true), Ok(1.5));
+		assert_eq!(strtod_strict("1.5 x", true), Err(ParseError::InvalidCharacter(4)));
+		assert_eq!(strtod_strict("  ", true), Err(ParseError::Empty(2)));
+	}
+	
+	#[test]
+	pub fn bytes_tests() {
+		assert_eq!(strtod_bytes(b"2.5\xff"), Some(2.5));
+		assert_eq!(strtod_bytes(b"\xff"), None);
+		assert_eq!(strtod_bytes(b"."), Some(0.0));
+		assert_eq!(strtod_bytes_prefix(b" 2.5\xff"), Some((2.5, 4)));
+		assert_eq!(try_strtod_bytes(b"-\xc3"), Err(ParseError::SignWithoutDigits(1)));
+		assert_eq!(strtod_bytes_strict(b"1e-7\n", true), Ok(1e-7));
+		assert_eq!(strtod_bytes_strict(b"1e-7\xa0", true), Err(ParseError::InvalidCharacter(4)));
+		assert_eq!(strtod_bytes_with(b"-1,5\xff", &ParseOptions::new().decimal_point(',')), Ok(-1.5));
+		assert_eq!(strtod_bytes_strict(b"1\0x", false), Err(ParseError::InvalidCharacter(1)));
+		assert_eq!(strtod_bytes_strict(b"0\0", false), Err(ParseError::InvalidCharacter(1)));
+		assert_eq!(strtod_bytes_strict(b"\0", false), Err(ParseError::InvalidCharacter(0)));
+		assert_eq!(strtod_bytes_strict(b" \0.5", false), Err(ParseError::InvalidCharacter(1)));
+		assert_eq!(strtod_bytes_strict(b"-\0", false), Err(ParseError::SignWithoutDigits(1)));
+	}
+	
+	#[test]
+	pub fn single_tests() {
+		assert_eq!(strtof("1.5"), Some(1.5));
+		assert_eq!(strtof("-0"), Some(-0.0));
+		assert_eq!(strtof("."), Some(0.0));
+		assert_eq!(strtof("x"), None);
+		assert_eq!(strtof("0.1"), Some(0.1));
+		assert_eq!(strtof("3.4028235e38"), Some(f32::MAX));
+		assert_eq!(strtof("3.40282356e38"), Some(f32::MAX));
+		assert_eq!(strtof("3.40282357e38"), Some(f32::INFINITY));
+		
+		// Around 2^128 - 2^103, halfway between the largest single and
+		// infinity; the double closest to the first two is the midpoint.
+		assert_eq!(strtof("340282356779733661637539395458142568447"), Some(f32::MAX));
+		assert_eq!(strtof("340282356779733661637539395458142568447.99"), Some(f32::MAX));
+		assert_eq!(strtof("340282356779733661637539395458142568448"), Some(f32::INFINITY));
+		assert_eq!(strtof("340282356779733661637539395458142568448.00001"), Some(f32::INFINITY));
+		assert_eq!(strtof("1e39"), Some(f32::INFINITY));
+		assert_eq!(strtof("1e400"), Some(f32::INFINITY));
+		assert_eq!(strtof("1.4e-45"), Some(f32::from_bits(1)));
+		assert_eq!(strtof("7e-46"), Some(0.0));
+		assert_eq!(strtof("7.1e-46"), Some(f32::from_bits(1)));
+		assert_eq!(strtof("1e-400"), Some(0.0));
+		
+		// Halfway between zero and the smallest subnormal single, and
+		// between the two smallest ones.
+		assert_eq!(strtof("7.00649232162408535461864791644958065640130970938257885878534141944895541342930300743319094181060791015625e-46"), Some(0.0));
+		assert_eq!(strtof("7.006492321624085354618647916449580656401309709382578858785341419448955413429303007433190941810607910156251e-46"), Some(f32::from_bits(1)));
+		assert_eq!(strtof("2.101947696487225606385594374934874196920392912814773657635602425834686624028790902229957282543182373046875e-45"), Some(f32::from_bits(2)));
+		
+		// Halfway between 1 and the next single; the double closest to
+		// these is the midpoint itself, so rounding twice goes wrong.
+		assert_eq!(strtof("1.000000059604644775390625"), Some(1.0));
+		assert_eq!(strtof("1.00000005960464477539062500001"), Some(f32::from_bits(0x3f800001)));
+		assert_eq!(strtof("1.00000017881393432617187499999"), Some(f32::from_bits(0x3f800001)));
+		assert_eq!(strtof("1.000000178813934326171875"), Some(f32::from_bits(0x3f800002)));
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		let options = ParseOptions::new().special_values(SpecialValues::C);
+		
+		assert_eq!(strtod_with("inf", &options), Ok(f64::INFINITY));
+		assert_eq!(strtod_with("  -Inf", &options), Ok(f64::NEG_INFINITY));
+		assert_eq!(strtod_with("+InFiNiTy", &options), Ok(f64::INFINITY));
+		assert_eq!(strtod_with("infinit", &options), Ok(f64::INFINITY));
+		assert_eq!(strtod_with("in", &options), Err(ParseError::InvalidCharacter(0)));
+		assert_eq!(strtod_with("1.5", &options), Ok(1.5));
+		assert_eq!(strtod_with("Inf", &ParseOptions::new()), Err(ParseError::InvalidCharacter(0)));
+		
+		let nan = strtod_with("NaN", &options).unwrap();
+		assert!(nan.is_nan() && nan.is_sign_positive());
+		assert_eq!(nan.to_bits(), 0x7ff8000000000000);
+		let nan = strtod_with("-nan", &options).unwrap();
+		assert!(nan.is_nan() && nan.is_sign_negative());
+		
+		assert_eq!(strtod_prefix_with("-infinity and beyond", &options), Ok((f64::NEG_INFINITY, 9)));
+		assert_eq!(strtod_bytes_prefix_with(b"nan\xff", &options).map(|(_, end)| end), Ok(3));
+		assert_eq!(strtof_with("inf", &options), Ok(f32::INFINITY));
+	}
+	
+	#[test]
+	pub fn nan_payload_tests() {
+		let options = ParseOptions::new().special_values(SpecialValues {
+			signaling_nan: &["snan"],
+			..SpecialValues::C
+		});
+		
+		test_bits(&options, "nan(0x1234)", Ok((0x7ff8000000001234, 11)));
+		test_bits(&options, "NAN(4660)", Ok((0x7ff8000000001234, 9)));
+		test_bits(&options, "nan(011064)", Ok((0x7ff8000000001234, 11)));
+		test_bits(&options, "-nan(1)", Ok((0xfff8000000000001, 7)));
+		test_bits(&options, "nan()", Ok((0x7ff8000000000000, 5)));
+		test_bits(&options, "nan(abc)", Ok((0x7ff8000000000000, 8)));
+		test_bits(&options, "nan(0x)", Ok((0x7ff8000000000000, 7)));
+		test_bits(&options, "nan(1", Ok((0x7ff8000000000000, 3)));
+		test_bits(&options, "nan(1 )", Ok((0x7ff8000000000000, 3)));
+		test_bits(&options, "nan(0xfffffffffffffffff)", Ok((0x7fffffffffffffff, 24)));
+		test_bits(&options, "nan(0x8000000000001)", Ok((0x7ff8000000000001, 20)));
+		test_bits(&options, "snan", Ok((0x7ff0000000000001, 4)));
+		test_bits(&options, "-SNaN(0x42)", Ok((0xfff0000000000042, 11)));
+		test_bits(&options, "snan(0x8000000000000)", Ok((0x7ff0000000000001, 21)));
+		
+		let no_payload = ParseOptions::new().special_values(SpecialValues {
+			nan_payload: false,
+			..SpecialValues::C
+		});
+		test_bits(&no_payload, "nan(0x1234)", Ok((0x7ff8000000000000, 3)));
+		test_bits(&ParseOptions::new().special_values(SpecialValues::C), "snan", Err(ParseError::InvalidCharacter(0)));
+		
+		assert_eq!(strtof_with("nan(0x12345)", &options).map(f32::to_bits), Ok(0x7fc12345));
+		assert_eq!(strtof_with("-snan", &options).map(f32::to_bits), Ok(0xff800001));
+		assert_eq!(strtof_with("snan(0x400000)", &options).map(f32::to_bits), Ok(0x7f800001));
+	}
+	
+	#[test]
+	pub fn hex_tests() {
+		let options = ParseOptions::new().hex_floats(true);
+		
+		test_bits(&options, "0x1.8p3", Ok((0x4028000000000000, 7)));
+		test_bits(&options, "0X1A.Cp-1", Ok((0x402ac00000000000, 9)));
+		test_bits(&options, "-0x1P-2", Ok((0xbfd0000000000000, 7)));
+		test_bits(&options, "0x.8", Ok((0x3fe0000000000000, 4)));
+		test_bits(&options, "0x", Ok((0, 1)));
+		test_bits(&options, "0x.", Ok((0, 1)));
+		test_bits(&options, "0x1p", Ok((0x3ff0000000000000, 3)));
+		test_bits(&options, "0x1p+", Ok((0x3ff0000000000000, 3)));
+		test_bits(&options, "0x1.00000000000008p0", Ok((0x3ff0000000000000, 20)));
+		test_bits(&options, "0x1.00000000000018p0", Ok((0x3ff0000000000002, 20)));
+		test_bits(&options, "0x1.000000000000080001p0", Ok((0x3ff0000000000001, 24)));
+		test_bits(&options, "0x1p-1074", Ok((0x0000000000000001, 9)));
+		test_bits(&options, "0x1p-1075", Ok((0, 9)));
+		test_bits(&options, "0x1.8p-1075", Ok((0x0000000000000001, 11)));
+		test_bits(&options, "0x1p-99999999999999", Ok((0, 19)));
+		test_bits(&options, "0x1.fffffffffffffp1023", Ok((0x7fefffffffffffff, 22)));
+		test_bits(&options, "0x1.fffffffffffff8p1023", Ok((0x7ff0000000000000, 23)));
+		test_bits(&options, "0x1p1024", Ok((0x7ff0000000000000, 8)));
+		
+		test("0x1.8p3", Some(0.0));
+		
+		let single = |input: &str| strtof_with(input, &options).map(f32::to_bits);
+		assert_eq!(single("0x1.fffffep127"), Ok(0x7f7fffff));
+		assert_eq!(single("0x1.fffffffp127"), Ok(0x7f800000));
+		assert_eq!(single("0x1p-149"), Ok(0x00000001));
+		assert_eq!(single("0x1p-150"), Ok(0));
+		assert_eq!(single("0x1.000001p0"), Ok(0x3f800000));
+		assert_eq!(single("0x1.000003p0"), Ok(0x3f800002));
+	}
+	
+	#[test]
+	pub fn decimal_point_tests() {
+		let comma = ParseOptions::new().decimal_point(',');
+		
+		test_bits(&comma, "2,5", Ok((2.5_f64.to_bits(), 3)));
+		test_bits(&comma, "-,5e1", Ok(((-5_f64).to_bits(), 5)));
+		test_bits(&comma, "3.5", Ok((3_f64.to_bits(), 1)));
+		test_bits(&comma, "0,", Ok((0, 2)));
+		test_bits(&comma, "2,2250738585072011e-308", Ok((2.2250738585072011e-308_f64.to_bits(), 23)));
+		test_bits(&comma, "1234567890123,4567890123456789", Ok((1234567890123.4567890123456789_f64.to_bits(), 30)));
+		test_bits(&comma, "0,1000000000000000055511151231257827021181583404541015625",
+			Ok((0.1_f64.to_bits(), 57)));
+		test_bits(&comma, "0,1000000000000000055511151231257827021181583404541015624",
+			Ok((0.1_f64.to_bits(), 57)));
+		test_bits(&ParseOptions::new().decimal_point(',').hex_floats(true), "0x1,8p1", Ok((3_f64.to_bits(), 7)));
+		
+		let arabic = ParseOptions::new().decimal_point('\u{66b}');
+		test_bits(&arabic, "2\u{66b}5", Ok((2.5_f64.to_bits(), 4)));
+		test_bits(&arabic, "9007199254740993\u{66b}000000000000001", Ok((9007199254740994_f64.to_bits(), 33)));
+		test_bits(&arabic, "1\u{66b}7976931348623158079e308", Ok((f64::MAX.to_bits(), 26)));
+		test_bits(&arabic, "3.5", Ok((3_f64.to_bits(), 1)));
+		test_bits(&arabic, "3\u{66a}5", Ok((3_f64.to_bits(), 1)));
+		
+		assert_eq!(strtof_with("1\u{66b}00000017881393432617187499", &arabic).map(f32::to_bits), Ok(0x3f800001));
+	}
+	
+	#[test]
+	pub fn group_separator_tests() {
+		let options = ParseOptions::new().group_separators(GroupSeparators::UNDERSCORE);
+		
+		test_bits(&options, "1_000_000.5", Ok((1000000.5_f64.to_bits(), 11)));
+		test_bits(&options, "-1_000.000_1e1_0", Ok(((-1000.0001e10_f64).to_bits(), 16)));
+		test_bits(&options, "0_0.0_0_1", Ok((0.001_f64.to_bits(), 9)));
+		test_bits(&options, "9_007_199_254_740_993.000_000_000_000_001", Ok((9007199254740994_f64.to_bits(), 41)));
+		test_bits(&options, "1e4_00", Ok((f64::INFINITY.to_bits(), 6)));
+		test_bits(&options, "1e1_000_000_000_000", Ok((f64::INFINITY.to_bits(), 19)));
+		test_bits(&options, "1,000", Ok((1_f64.to_bits(), 1)));
+		
+		let error = |input| strtod_with(input, &options).map_err(|e| e.offset());
+		assert_eq!(error("_1"), Err(0));
+		assert_eq!(error("-_1"), Err(1));
+		assert_eq!(error("1_"), Err(1));
+		assert_eq!(error("1__0"), Err(2));
+		assert_eq!(error("1_.5"), Err(1));
+		assert_eq!(error("1._5"), Err(2));
+		assert_eq!(error("1.5_"), Err(3));
+		assert_eq!(error("1_e5"), Err(1));
+		assert_eq!(error("1e_5"), Err(2));
+		assert_eq!(error("1e-_5"), Err(3));
+		assert_eq!(error("1e5_"), Err(3));
+		assert_eq!(strtod_with("1__0", &options), Err(ParseError::MisplacedSeparator(2)));
+		assert_eq!(ParseError::MisplacedSeparator(2).to_string(), "misplaced group separator at offset 2");
+		
+		let lax = ParseOptions::new().group_separators(GroupSeparators {
+			leading: true,
+			trailing: true,
+			consecutive: true,
+			..GroupSeparators::UNDERSCORE
+		});
+		test_bits(&lax, "__1__.__5__e__1__", Ok((15_f64.to_bits(), 17)));
+		test_bits(&lax, "_", Err(ParseError::NoDigits(0)));
+		
+		let thousands = ParseOptions::new().group_separators(GroupSeparators {
+			characters: &[',', '\u{202f}'],
+			..GroupSeparators::UNDERSCORE
+		});
+		test_bits(&thousands, "1,234,567.89", Ok((1234567.89_f64.to_bits(), 12)));
+		test_bits(&thousands, "1\u{202f}234\u{202f}567.89", Ok((1234567.89_f64.to_bits(), 16)));
+		
+		let european = thousands.clone().decimal_point(',').group_separators(GroupSeparators {
+			characters: &['.', ','],
+			..GroupSeparators::UNDERSCORE
+		});
+		test_bits(&european, "1.234.567,89", Ok((1234567.89_f64.to_bits(), 12)));
+		
+		let hex = options.clone().hex_floats(true);
+		test_bits(&hex, "0x1_8p1_0", Ok((24576_f64.to_bits(), 9)));
+		assert_eq!(strtod_with("0x_1p0", &hex), Err(ParseError::MisplacedSeparator(2)));
+		
+		assert_eq!(strtof_with("1.000_000_178_813_934_326_171_874_99", &options).map(f32::to_bits), Ok(0x3f800001));
+		
+		test("1_000", Some(1.0));
+	}
+	
+	#[test]
+	pub fn rounding_mode_tests() {
+		use super::RoundingMode::*;
+		
+		let bits = |mode, input| {
+			let options = ParseOptions::new().hex_floats(true).rounding_mode(mode);
+			strtod_with(input, &options).map(f64::to_bits).unwrap()
+		};
+		
+		// Rounded up to nearest, so only the modes that round down differ.
+		assert_eq!(bits(NearestEven, "0.1"), 0x3fb999999999999a);
+		assert_eq!(bits(NearestAway, "0.1"), 0x3fb999999999999a);
+		assert_eq!(bits(TowardPositive, "0.1"), 0x3fb999999999999a);
+		assert_eq!(bits(TowardNegative, "0.1"), 0x3fb9999999999999);
+		assert_eq!(bits(TowardZero, "0.1"), 0x3fb9999999999999);
+		assert_eq!(bits(TowardPositive, "-0.1"), 0xbfb9999999999999);
+		assert_eq!(bits(TowardNegative, "-0.1"), 0xbfb999999999999a);
+		
+		// The fast path rounds down to nearest.
+		assert_eq!(bits(TowardNegative, "0.3"), 0x3fd3333333333333);
+		assert_eq!(bits(TowardPositive, "0.3"), 0x3fd3333333333334);
+		assert_eq!(bits(TowardPositive, "1.5"), 0x3ff8000000000000);
+		assert_eq!(bits(TowardZero, "-1.5"), 0xbff8000000000000);
+		
+		assert_eq!(bits(NearestEven, "9007199254740993"), 0x4340000000000000);
+		assert_eq!(bits(NearestAway, "9007199254740993"), 0x4340000000000001);
+		assert_eq!(bits(NearestAway, "-9007199254740993"), 0xc340000000000001);
+		assert_eq!(bits(NearestAway, "9007199254740993.000000000000000000000001"), 0x4340000000000001);
+		assert_eq!(bits(NearestAway, "9007199254740992.999999999999999999999999"), 0x4340000000000000);
+		
+		assert_eq!(bits(NearestEven, "1e-400"), 0);
+		assert_eq!(bits(TowardPositive, "1e-400"), 1);
+		assert_eq!(bits(TowardNegative, "-1e-400"), 0x8000000000000001);
+		assert_eq!(bits(TowardZero, "-1e-400"), 0x8000000000000000);
+		assert_eq!(bits(TowardPositive, "0e-400"), 0);
+		
+		assert_eq!(bits(TowardPositive, "1.7976931348623158e308"), 0x7ff0000000000000);
+		assert_eq!(bits(TowardZero, "1.7976931348623158e308"), 0x7fefffffffffffff);
+		assert_eq!(bits(TowardZero, "1e400"), 0x7fefffffffffffff);
+		assert_eq!(bits(TowardPositive, "-1e400"), 0xffefffffffffffff);
+		assert_eq!(bits(TowardNegative, "-1e400"), 0xfff0000000000000);
+		assert_eq!(bits(NearestAway, "1e400"), 0x7ff0000000000000);
+		
+		// 5 * 2^-1075, halfway between two denormals
+		let tie = "1.235164114603116360441421982170553430912649506535811911063964206251688768175521\
+			87966324959090408998094949141173861429432731664177588984949099693699002695469531\
+			57517829757785113196145429196224552592217965901424968268076250159685228839124609\
+			68281183493182924037850079288463495185315596413977927566646391716920467598900776\
+			56232986317897873113832326364136100281870032427499885482997352270104140831131189\
+			28696725368169503983880965288753370088162336800484475670267768729258330567111883\
+			33930208107984023095723364592015026502876542452438269585569329582311976245631182\
+			69409398181196866402119455093361742488341175449316942939628141513779978287622277\
+			53627594656845418127389593474333997484162024852910514256592725698106918861413072\
+			7188467062660492956638336181640625e-323";
+		assert_eq!(bits(NearestEven, tie), 2);
+		assert_eq!(bits(NearestAway, tie), 3);
+		
+		assert_eq!(bits(NearestAway, "0x1.00000000000008p0"), 0x3ff0000000000001);
+		assert_eq!(bits(TowardPositive, "0x1.000000000000000000001p0"), 0x3ff0000000000001);
+		assert_eq!(bits(TowardNegative, "0x1.fffffffffffffffffffffp0"), 0x3fffffffffffffff);
+		assert_eq!(bits(TowardPositive, "0x1p-2000"), 1);
+		assert_eq!(bits(NearestAway, "0x1p-1075"), 1);
+		assert_eq!(bits(TowardZero, "0x1p1024"), 0x7fefffffffffffff);
+		assert_eq!(bits(TowardNegative, "-0x1p1024"), 0xfff0000000000000);
+		
+		let single = |mode, input: &str| {
+			let options = ParseOptions::new().rounding_mode(mode);
+			strtof_with(input, &options).map(f32::to_bits).unwrap()
+		};
+		assert_eq!(single(TowardNegative, "0.1"), 0x3dcccccc);
+		assert_eq!(single(TowardPositive, "0.1"), 0x3dcccccd);
+		assert_eq!(single(NearestAway, "16777217"), 0x4b800001);
+		assert_eq!(single(NearestEven, "16777217"), 0x4b800000);
+		assert_eq!(single(TowardPositive, "1e-50"), 0x00000001);
+		assert_eq!(single(TowardPositive, "3.4028235677973366e38"), 0x7f800000);
+		assert_eq!(single(TowardZero, "3.4028235677973366e38"), 0x7f7fffff);
+		assert_eq!(single(TowardZero, "1e39"), 0x7f7fffff);
+		
+		assert_eq!(RoundingMode::default(), NearestEven);
+	}
+	
+	#[test]
+	pub fn conversion_tests() {
+		use super::Exactness::*;
+		use super::Range::*;
+		
+		let options = ParseOptions::new().hex_floats(true).special_values(SpecialValues::C);
+		let convert = |input| strtod_conversion(input, &options).unwrap();
+		
+		assert_eq!(convert("0.1"), Conversion { value: 0.1, exactness: RoundedUp, was_tie: false, range: Normal });
+		assert_eq!(convert("-0.1"), Conversion { value: -0.1, exactness: RoundedDown, was_tie: false, range: Normal });
+		assert_eq!(convert("0.3"), Conversion { value: 0.3, exactness: RoundedDown, was_tie: false, range: Normal });
+		assert_eq!(convert("0.5"), Conversion { value: 0.5, exactness: Exact, was_tie: false, range: Normal });
+		assert_eq!(convert("1e22"), Conversion { value: 1e22, exactness: Exact, was_tie: false, range: Normal });
+		assert_eq!(convert("1e23"), Conversion { value: 1e23, exactness: RoundedDown, was_tie: true, range: Normal });
+		assert_eq!(convert("9007199254740993"), Conversion { value: 9007199254740992.0, exactness: RoundedDown, was_tie: true, range: Normal });
+		assert_eq!(convert("-9007199254740995"), Conversion { value: -9007199254740996.0, exactness: RoundedDown, was_tie: true, range: Normal });
+		assert_eq!(convert("0x1.00000000000008p0"), Conversion { value: 1.0, exactness: RoundedDown, was_tie: true, range: Normal });
+		assert_eq!(convert("0x1.00000000000009p0").exactness, RoundedUp);
+		assert_eq!(convert("0x1.8p-1074"), Conversion { value: 1e-323, exactness: RoundedUp, was_tie: true, range: Subnormal });
+		
+		assert_eq!(convert("1e400"), Conversion { value: f64::INFINITY, exactness: RoundedUp, was_tie: false, range: Overflow });
+		assert_eq!(convert("-0x1p1024").exactness, RoundedDown);
+		assert_eq!(convert("0x1.fffffffffffff8p1023"), Conversion { value: f64::INFINITY, exactness: RoundedUp, was_tie: true, range: Overflow });
+		assert!(!convert("0x1.fffffffffffff8p1024").was_tie);
+		assert_eq!(convert("1e-400"), Conversion { value: 0.0, exactness: RoundedDown, was_tie: false, range: Underflow });
+		assert_eq!(convert("-1e-400").exactness, RoundedUp);
+		
+		assert!(convert("0").is_exact());
+		assert!(convert("-0e5").is_exact());
+		assert!(convert("inf").is_exact());
+		assert!(convert("nan").is_exact());
+		assert!(convert("0x1p-1074").is_exact());
+		assert!(!convert("0.1").is_exact());
+		
+		let away = ParseOptions::new().rounding_mode(RoundingMode::NearestAway);
+		assert_eq!(strtod_conversion("9007199254740993", &away),
+			Ok(Conversion { value: 9007199254740994.0, exactness: RoundedUp, was_tie: true, range: Normal }));
+		let down = ParseOptions::new().rounding_mode(RoundingMode::TowardNegative);
+		assert_eq!(strtod_conversion("1e400", &down),
+			Ok(Conversion { value: f64::MAX, exactness: RoundedDown, was_tie: false, range: Overflow }));
+		assert_eq!(strtod_conversion("0.5", &down).map(|c| c.exactness), Ok(Exact));
+		
+		assert_eq!(strtof_conversion("0.1", &options).map(|c| (c.value, c.exactness)), Ok((0.1_f32, RoundedUp)));
+		assert_eq!(strtof_conversion("1e-50", &options),
+			Ok(Conversion { value: 0.0_f32, exactness: RoundedDown, was_tie: false, range: Underflow }));
+		assert_eq!(strtof_conversion("340282356779733661637539395458142568447", &options),
+			Ok(Conversion { value: f32::MAX, exactness: RoundedDown, was_tie: false, range: Normal }));
+		assert_eq!(strtof_conversion("340282356779733661637539395458142568448", &options),
+			Ok(Conversion { value: f32::INFINITY, exactness: RoundedUp, was_tie: true, range: Overflow }));
+		
+		assert_eq!(strtod_conversion("x", &options), Err(ParseError::InvalidCharacter(0)));
+	}
+	
+	#[test]
+	pub fn overflow_tests() {
+		let saturate = ParseOptions::new().hex_floats(true).overflow(Overflow::Saturate);
+		assert_eq!(strtod_with("1e400", &saturate), Ok(f64::MAX));
+		assert_eq!(strtod_with("-1e400", &saturate), Ok(-f64::MAX));
+		assert_eq!(strtod_with("0x1p1024", &saturate), Ok(f64::MAX));
+		assert_eq!(strtod_with("1.7976931348623157e308", &saturate), Ok(f64::MAX));
+		assert_eq!(strtod_with("1e-400", &saturate), Ok(0.0));
+		assert_eq!(strtod_conversion("1e400", &saturate).map(|c| (c.exactness, c.range)),
+			Ok((Exactness::RoundedDown, Range::Overflow)));
+		
+		assert_eq!(strtof_with("1e39", &saturate), Ok(f32::MAX));
+		
+		let error = ParseOptions::new().overflow(Overflow::Error).special_values(SpecialValues::C);
+		assert_eq!(strtod_with("1e400", &error), Err(ParseError::OutOfRange(0)));
+		assert_eq!(strtod_with("  -1e400", &error), Err(ParseError::OutOfRange(3)));
+		assert_eq!(strtod_with("-inf", &error), Ok(f64::NEG_INFINITY));
+		assert_eq!(strtod_with("1e-400", &error), Ok(0.0));
+		assert_eq!(strtod_with("1.7976931348623158e308", &error), Ok(f64::MAX));
+		assert_eq!(strtod_with("1.797693134862315808e308", &error), Err(ParseError::OutOfRange(0)));
+		assert_eq!(ParseError::OutOfRange(0).to_string(), "number out of range at offset 0");
+		
+		let toward_zero = error.clone().rounding_mode(RoundingMode::TowardZero);
+		assert_eq!(strtod_with("1.797693134862315807e308", &toward_zero), Ok(f64::MAX));
+		assert_eq!(strtod_with("1e400", &toward_zero), Err(ParseError::OutOfRange(0)));
+		
+		let range = |input| strtod_conversion(input, &ParseOptions::new()).unwrap().range;
+		assert_eq!(range("1"), Range::Normal);
+		assert_eq!(range("0"), Range::Normal);
+		assert_eq!(range("1e-400"), Range::Underflow);
+		assert_eq!(range("2.4703282292062327e-324"), Range::Underflow);
+		assert_eq!(range("2.4703282292062328e-324"), Range::Subnormal);
+		assert_eq!(range("2.2250738585072011e-308"), Range::Subnormal);
+		assert_eq!(range("2.2250738585072012e-308"), Range::Normal);
+		assert_eq!(range("1e400"), Range::Overflow);
+		
+		// 2^1024
+		let two_1024 = "17976931348623159077293051907890247336179769789423065727343008115773267580550096\
+			31327084773224075360211201138798713933576587897688144166224928474306394741243777\
+			67893424865485276302219601246094119453082952085005768838150682342462881473913110\
+			540827237163350510684586298239947245938479716304835356329624224137216";
+		assert_eq!(strtod_conversion(two_1024, &ParseOptions::new()),
+			Ok(Conversion { value: f64::INFINITY, exactness: Exactness::RoundedUp, was_tie: false, range: Range::Overflow }));
+		assert_eq!(strtod_with(two_1024, &toward_zero), Err(ParseError::OutOfRange(0)));
+	}
+	
+	#[test]
+	pub fn grammar_tests() {
+		let options = ParseOptions::new().skip_whitespace(false);
+		assert_eq!(strtod_with(" 1", &options), Err(ParseError::InvalidCharacter(0)));
+		assert_eq!(strtod_with("-\t1", &options), Err(ParseError::SignWithoutDigits(1)));
+		assert_eq!(strtod_with("1", &options), Ok(1.0));
+		
+		let options = ParseOptions::new().leading_plus(false);
+		assert_eq!(strtod_with("+1", &options), Err(ParseError::InvalidCharacter(0)));
+		assert_eq!(strtod_with(" +1", &options), Err(ParseError::InvalidCharacter(1)));
+		assert_eq!(strtod_with("-1", &options), Ok(-1.0));
+		
+		let options = ParseOptions::new().leading_decimal_point(false);
+		assert_eq!(strtod_with(".5", &options), Err(ParseError::NoDigits(0)));
+		assert_eq!(strtod_with("-.5", &options), Err(ParseError::NoDigits(1)));
+		assert_eq!(strtod_with("0.5", &options), Ok(0.5));
+		assert_eq!(strtod_with("5.", &options), Ok(5.0));
+		
+		let options = ParseOptions::new().trailing_decimal_point(false);
+		assert_eq!(strtod_with("5.", &options), Err(ParseError::NoDigits(2)));
+		assert_eq!(strtod_with("5.e3", &options), Err(ParseError::NoDigits(2)));
+		assert_eq!(strtod_with("5.0", &options), Ok(5.0));
+		assert_eq!(strtod_with(".5", &options), Ok(0.5));
+		
+		let options = ParseOptions::new().leading_zeros(false);
+		assert_eq!(strtod_with("0123", &options), Err(ParseError::LeadingZero(0)));
+		assert_eq!(strtod_with("-00.5", &options), Err(ParseError::LeadingZero(1)));
+		assert_eq!(strtod_with("0", &options), Ok(0.0));
+		assert_eq!(strtod_with("-0", &options).map(f64::is_sign_negative), Ok(true));
+		assert_eq!(strtod_with("0.05", &options), Ok(0.05));
+		assert_eq!(strtod_with("0e5", &options), Ok(0.0));
+		assert_eq!(strtod_with("100", &options), Ok(100.0));
+		assert_eq!(strtod_with("1e05", &options), Ok(1e5));
+		assert_eq!(ParseError::LeadingZero(0).to_string(), "leading zero at offset 0");
+		
+		let options = ParseOptions::new().exponent_backoff(false).hex_floats(true);
+		assert_eq!(strtod_with("1e", &options), Err(ParseError::DanglingExponent(1)));
+		assert_eq!(strtod_with("1e+", &options), Err(ParseError::DanglingExponent(1)));
+		assert_eq!(strtod_with("1.5E-x", &options), Err(ParseError::DanglingExponent(3)));
+		assert_eq!(strtod_with("0x1p", &options), Err(ParseError::DanglingExponent(3)));
+		assert_eq!(strtod_with("1e5", &options), Ok(1e5));
+		
+		let options = ParseOptions::new().exponent_markers(&['d', 'D', 'e', 'E']);
+		test_bits(&options, "1.5d3", Ok((1500_f64.to_bits(), 5)));
+		test_bits(&options, "1.5D-1", Ok((0.15_f64.to_bits(), 6)));
+		test_bits(&options, "1.5e1", Ok((15_f64.to_bits(), 5)));
+		test_bits(&options, "1.5d", Ok((1.5_f64.to_bits(), 3)));
+		
+		let options = ParseOptions::new().exponent_markers(&['\u{23e8}']);
+		test_bits(&options, "1.5\u{23e8}3", Ok((1500_f64.to_bits(), 7)));
+		test_bits(&options, "1.5e3", Ok((1.5_f64.to_bits(), 3)));
+		let mut parser = Parser::new(&options);
+		assert_eq!(parser.parse_strict("1\u{23e8}".as_bytes(), false), Err(ParseError::DanglingExponent(1)));
+		let mut parser = Parser::new(&options);
+		assert_eq!(parser.parse_strict(b"1e", false), Err(ParseError::InvalidCharacter(1)));
+		
+		let json = ParseOptions::new()
+			.skip_whitespace(false)
+			.leading_plus(false)
+			.leading_decimal_point(false)
+			.trailing_decimal_point(false)
+			.leading_zeros(false)
+			.exponent_backoff(false);
+		assert_eq!(strtod_with("-0.25e+2", &json), Ok(-25.0));
+		assert_eq!(strtod_with("-01", &json), Err(ParseError::LeadingZero(1)));
+	}
+	
+	fn test_bits(options: &ParseOptions, input: &str, expected: Result<(u64, usize), ParseError>) {
+		let result = strtod_prefix_with(input, options).map(|(d, end)| (d.to_bits(), end));
+		assert_eq!(result, expected, "{:?}", input);
+	}
+	
+	fn test_prefix(input: &str, val: Option<(f64, usize)>) {
+		let result = strtod_prefix(input);
+		assert_eq!(result, val);
+		if result.is_some() {
+			assert_eq!(result.unwrap().0.is_sign_positive(), val.unwrap().0.is_sign_positive());
+		}
+	}
+	
 	fn test(input: &str, val: Option<f64>) {
 		let result = strtod(input);
 		assert_eq!(result, val);
@@ -1474,4 +3538,65 @@ mod test {
 			assert_eq!(result.unwrap().is_sign_positive(), val.unwrap().is_sign_positive());
 		}
 	}
+	
+	// The results of the dialect modules are compared by their bits, so
+	// that the sign of zero and the NaN payload are checked too.
+	pub trait Bits {
+		type Bits: PartialEq + Debug;
+		
+		fn bits(&self) -> Self::Bits;
+	}
+	
+	impl Bits for f64 {
+		type Bits = u64;
+		
+		fn bits(&self) -> u64 {
+			self.to_bits()
+		}
+	}
+	
+	impl Bits for f32 {
+		type Bits = u32;
+		
+		fn bits(&self) -> u32 {
+			self.to_bits()
+		}
+	}
+	
+	impl<T: Bits> Bits for Option<T> {
+		type Bits = Option<T::Bits>;
+		
+		fn bits(&self) -> Option<T::Bits> {
+			self.as_ref().map(T::bits)
+		}
+	}
+	
+	impl<T: Bits, E: Clone + PartialEq + Debug> Bits for Result<T, E> {
+		type Bits = Result<T::Bits, E>;
+		
+		fn bits(&self) -> Result<T::Bits, E> {
+			self.as_ref().map(T::bits).map_err(E::clone)
+		}
+	}
+	
+	impl<T: Bits, U: Clone + PartialEq + Debug> Bits for (T, U) {
+		type Bits = (T::Bits, U);
+		
+		fn bits(&self) -> (T::Bits, U) {
+			(self.0.bits(), self.1.clone())
+		}
+	}
+	
+	impl<T: Bits, U: Clone + PartialEq + Debug, V: Clone + PartialEq + Debug> Bits for (T, U, V) {
+		type Bits = (T::Bits, U, V);
+		
+		fn bits(&self) -> (T::Bits, U, V) {
+			(self.0.bits(), self.1.clone(), self.2.clone())
+		}
+	}
+	
+	pub fn check<T: Bits + Debug>(f: impl Fn(&str) -> T, input: &str, expected: T) {
+		let result = f(input);
+		assert_eq!(result.bits(), expected.bits(), "{:?} gave {:?}", input, result);
+	}
 }
@@ -0,0 +1,163 @@
+//! Python's `float()` applied to a string.
+
+use super::{GroupSeparators, ParseError, ParseOptions, Parser, SpecialValues};
+
+static OPTIONS : ParseOptions = ParseOptions {
+	special_values: SpecialValues {
+		infinity: &["infinity", "inf"],
+		nan: &["nan"],
+		signaling_nan: &[],
+		nan_payload: false,
+		ignore_case: true
+	},
+	group_separators: GroupSeparators::UNDERSCORE,
+	skip_whitespace: false,
+	exponent_backoff: false,
+	..ParseOptions::new()
+};
+
+// The zeros of the non-ASCII decimal digits (general category Nd) in
+// Unicode 14.0, which always come in runs from zero to nine.
+static ZEROS : [char; 65] = [
+	'\u{660}', '\u{6f0}', '\u{7c0}', '\u{966}', '\u{9e6}', '\u{a66}',
+	'\u{ae6}', '\u{b66}', '\u{be6}', '\u{c66}', '\u{ce6}', '\u{d66}',
+	'\u{de6}', '\u{e50}', '\u{ed0}', '\u{f20}', '\u{1040}', '\u{1090}',
+	'\u{17e0}', '\u{1810}', '\u{1946}', '\u{19d0}', '\u{1a80}', '\u{1a90}',
+	'\u{1b50}', '\u{1bb0}', '\u{1c40}', '\u{1c50}', '\u{a620}', '\u{a8d0}',
+	'\u{a900}', '\u{a9d0}', '\u{a9f0}', '\u{aa50}', '\u{abf0}', '\u{ff10}',
+	'\u{104a0}', '\u{10d30}', '\u{11066}', '\u{110f0}', '\u{11136}',
+	'\u{111d0}', '\u{112f0}', '\u{11450}', '\u{114d0}', '\u{11650}',
+	'\u{116c0}', '\u{11730}', '\u{118e0}', '\u{11950}', '\u{11c50}',
+	'\u{11d50}', '\u{11da0}', '\u{16a60}', '\u{16ac0}', '\u{16b50}',
+	'\u{1d7ce}', '\u{1d7d8}', '\u{1d7e2}', '\u{1d7ec}', '\u{1d7f6}',
+	'\u{1e140}', '\u{1e2f0}', '\u{1e950}', '\u{1fbf0}'
+];
+
+/// Converts a string to a number like `float(string)` in Python 3.
+///
+/// * Whitespace is stripped from both ends. This is ASCII whitespace and
+///   the non-ASCII Unicode whitespace like U+00A0 and U+3000, but not the
+///   ASCII control characters U+001C to U+001F.
+/// * Unicode decimal digits like `١٢٣` or `１２` can be used in place of
+///   ASCII digits.
+/// * Underscores are only allowed between two digits, like `1_000.000_1`.
+/// * `inf`, `infinity` and `nan` are accepted in any case with an
+///   optional sign. A NaN payload like `nan(1)` is not.
+/// * The exponent must have digits, and nothing may follow the number.
+///
+/// Errors carry byte offsets into `input`.
+///
+/// ```
+/// use strtod::python::float;
+///
+/// assert_eq!(float(" 1_000.5\n"), Ok(1000.5));
+/// assert_eq!(float("１２.５"), Ok(12.5));
+/// assert!(float("1__000").is_err());
+/// ```
+pub fn float(input: &str) -> Result<f64, ParseError> {
+	let trimmed = input.trim_start_matches(is_whitespace);
+	let start = input.len() - trimmed.len();
+	let trimmed = trimmed.trim_end_matches(is_whitespace);
+	
+	// Like CPython, the digits are mapped to ASCII and any other non-ASCII
+	// character to one that is never valid. Offsets maps the bytes back
+	// to the input.
+	let mut bytes = Vec::with_capacity(trimmed.len());
+	let mut offsets = Vec::with_capacity(trimmed.len() + 1);
+	
+	for (i, c) in trimmed.char_indices() {
+		bytes.push(if c.is_ascii() { c as u8 } else { digit(c).map_or(b'?', |d| b'0' + d) });
+		offsets.push(start + i);
+	}
+	offsets.push(start + trimmed.len());
+	
+	let mut parser = Parser::new(&OPTIONS);
+	
+	parser.parse_strict(&bytes, false).map_err(|error| error.with_offset(offsets[error.offset()]))
+}
+
+fn digit(c: char) -> Option<u8> {
+	let i = match ZEROS.binary_search(&c) {
+		Ok(i) => i,
+		Err(0) => return None,
+		Err(i) => i - 1
+	};
+	
+	let d = c as u32 - ZEROS[i] as u32;
+	if d < 10 { Some(d as u8) } else { None }
+}
+
+fn is_whitespace(c: char) -> bool {
+	matches!(c, '\t' ..= '\r' | ' ') || (!c.is_ascii() && c.is_whitespace())
+}
+
+#[cfg(test)]
+mod test {
+	use super::float;
+	use test::check;
+	use ParseError;
+	use std::f64;
+	
+	#[test]
+	pub fn decimal_tests() {
+		check(float, "12.5", Ok(12.5));
+		check(float, "-0", Ok(-0.0));
+		check(float, "+.5", Ok(0.5));
+		check(float, "5.", Ok(5.0));
+		check(float, "007", Ok(7.0));
+		check(float, "1e400", Ok(f64::INFINITY));
+		check(float, ".", Err(ParseError::NoDigits(0)));
+		check(float, "+", Err(ParseError::SignWithoutDigits(1)));
+		check(float, "1e", Err(ParseError::DanglingExponent(1)));
+		check(float, "1e+", Err(ParseError::DanglingExponent(1)));
+		check(float, "0x10", Err(ParseError::InvalidCharacter(1)));
+	}
+	
+	#[test]
+	pub fn whitespace_tests() {
+		check(float, " \t1\n\u{85}\u{a0}\u{3000}", Ok(1.0));
+		check(float, "", Err(ParseError::Empty(0)));
+		check(float, " \u{3000}", Err(ParseError::Empty(4)));
+		check(float, " 1 2", Err(ParseError::InvalidCharacter(2)));
+		check(float, "1\u{1c}", Err(ParseError::InvalidCharacter(1)));
+		check(float, "\u{1c}1", Err(ParseError::InvalidCharacter(0)));
+		check(float, "1\u{180e}", Err(ParseError::InvalidCharacter(1)));
+		check(float, "1\u{0}", Err(ParseError::InvalidCharacter(1)));
+	}
+	
+	#[test]
+	pub fn unicode_digit_tests() {
+		check(float, "١٢٣", Ok(123.0));
+		check(float, "１２.５e１", Ok(125.0));
+		check(float, "١_٢", Ok(12.0));
+		check(float, "\u{1d7d9}\u{1fbf9}", Ok(19.0));
+		check(float, "\u{a0}１２x", Err(ParseError::InvalidCharacter(8)));
+		check(float, "1\u{301}", Err(ParseError::InvalidCharacter(1)));
+	}
+	
+	#[test]
+	pub fn underscore_tests() {
+		check(float, "1_0", Ok(10.0));
+		check(float, "1e1_0", Ok(10000000000.0));
+		check(float, "0_0.0_0", Ok(0.0));
+		check(float, "1__0", Err(ParseError::MisplacedSeparator(2)));
+		check(float, "_1", Err(ParseError::MisplacedSeparator(0)));
+		check(float, "1_", Err(ParseError::MisplacedSeparator(1)));
+		check(float, "1_.5", Err(ParseError::MisplacedSeparator(1)));
+		check(float, "1._5", Err(ParseError::MisplacedSeparator(2)));
+		check(float, "1e_5", Err(ParseError::MisplacedSeparator(2)));
+		check(float, "1_e5", Err(ParseError::MisplacedSeparator(1)));
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		check(float, "inf", Ok(f64::INFINITY));
+		check(float, "-Infinity", Ok(f64::NEG_INFINITY));
+		check(float, "+INF", Ok(f64::INFINITY));
+		check(float, "nan", Ok(f64::NAN));
+		check(float, "-NaN", Ok(-f64::NAN));
+		check(float, "infinit", Err(ParseError::InvalidCharacter(3)));
+		check(float, "nan(1)", Err(ParseError::InvalidCharacter(3)));
+		check(float, "in_f", Err(ParseError::InvalidCharacter(0)));
+	}
+}
@@ -0,0 +1,194 @@
+//! Floating point literals in Rust source code.
+
+use super::{narrow, GroupSeparators, ParseError, ParseOptions, Parser};
+
+// Underscores are skipped anywhere after the first digit. The only place
+// where Rust is stricter is right after the decimal point.
+static OPTIONS : ParseOptions = ParseOptions {
+	group_separators: GroupSeparators {
+		characters: &['_'],
+		leading: true,
+		trailing: true,
+		consecutive: true
+	},
+	skip_whitespace: false,
+	leading_plus: false,
+	exponent_backoff: false,
+	..ParseOptions::new()
+};
+
+/// The floating point types of Rust.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FloatType {
+	/// `f32`
+	F32,
+	/// `f64`
+	F64
+}
+
+/// A parsed floating point literal.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Literal {
+	/// The value, rounded directly to the type of the literal. For `f32`
+	/// it is exactly representable as an `f32`.
+	pub value: f64,
+	/// The type suffix, like `f32` in `1.5_f32`, if there is one.
+	pub suffix: Option<FloatType>
+}
+
+/// Parses a floating point literal with the lexical grammar of Rust.
+///
+/// * The literal starts with a decimal digit. There is no sign, as `-` is
+///   an operator in Rust.
+/// * Underscores may appear anywhere after the first digit, like
+///   `1_000.000_1` or `1__e_5`, except right after the decimal point.
+/// * A decimal point must be followed by a digit, or end the literal like
+///   in `1.`: `1.e5` and `1.f32` are method calls in Rust.
+/// * The exponent must have at least one digit, like `1e_5` but not
+///   `1e_`.
+/// * The literal may end with an `f32` or `f64` suffix, with or without
+///   underscores in front. A literal without a decimal point or an
+///   exponent is an integer literal unless it has a suffix, so `1f32` is
+///   accepted but `1` is reported as `ParseError::MissingExponent`.
+///
+/// The value is rounded directly to the type of the suffix, or to
+/// `unsuffixed` when there is none, which is where the caller puts the
+/// type that the literal is inferred to have. Literals too large for the
+/// type are infinite; `rustc` rejects them with the `overflowing_literals`
+/// lint.
+///
+/// ```
+/// use strtod::rust::{parse_literal, FloatType};
+///
+/// let literal = parse_literal("1_000.5e-3_f32", FloatType::F64).unwrap();
+/// assert_eq!(literal.value, 1.0005_f32 as f64);
+/// assert_eq!(literal.suffix, Some(FloatType::F32));
+///
+/// assert_eq!(parse_literal("2.5", FloatType::F64).unwrap().suffix, None);
+/// assert!(parse_literal("1.f64", FloatType::F64).is_err());
+/// ```
+pub fn parse_literal(input: &str, unsuffixed: FloatType) -> Result<Literal, ParseError> {
+	let input = input.as_bytes();
+	
+	if input.is_empty() {
+		return Err(ParseError::Empty(0));
+	}
+	
+	if !input[0].is_ascii_digit() {
+		return Err(ParseError::InvalidCharacter(0));
+	}
+	
+	let (float_type, suffix) = match suffix(input) {
+		Some((float_type, start)) => (float_type, Some(start)),
+		None => (unsuffixed, None)
+	};
+	
+	let mut parser = Parser::new(&OPTIONS);
+	parser.single = float_type == FloatType::F32;
+	
+	let d = parser.parse(input)?;
+	let number = &input[..parser.end];
+	
+	if let Some(dot) = number.iter().position(|&c| c == b'.') {
+		if dot + 1 < input.len() && !input[dot + 1].is_ascii_digit() {
+			return Err(ParseError::InvalidCharacter(dot + 1));
+		}
+	}
+	
+	match suffix {
+		Some(start) if parser.end == start => {}
+		_ if parser.end < input.len() => return Err(ParseError::InvalidCharacter(parser.end)),
+		_ if !number.iter().any(|&c| matches!(c, b'.' | b'e' | b'E')) => {
+			return Err(ParseError::MissingExponent(parser.end));
+		}
+		_ => {}
+	}
+	
+	Ok(Literal {
+		value: if float_type == FloatType::F32 { narrow(d) as f64 } else { d },
+		suffix: suffix.map(|_| float_type)
+	})
+}
+
+// The type and offset of the suffix that ends the input.
+fn suffix(input: &[u8]) -> Option<(FloatType, usize)> {
+	let start = input.len().checked_sub(3)?;
+	
+	match &input[start..] {
+		b"f32" => Some((FloatType::F32, start)),
+		b"f64" => Some((FloatType::F64, start)),
+		_ => None
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::{parse_literal, FloatType};
+	use super::FloatType::*;
+	use test::check;
+	use ParseError;
+	use std::f64;
+	
+	#[test]
+	pub fn decimal_tests() {
+		check(inferred_f64, "1.5", Ok((1.5, None)));
+		check(inferred_f64, "1.", Ok((1.0, None)));
+		check(inferred_f64, "007.5", Ok((7.5, None)));
+		check(inferred_f64, "1e5", Ok((100000.0, None)));
+		check(inferred_f64, "1E+5", Ok((100000.0, None)));
+		check(inferred_f64, "1e-1", Ok((0.1, None)));
+		check(inferred_f64, "1e400", Ok((f64::INFINITY, None)));
+		check(inferred_f64, "", Err(ParseError::Empty(0)));
+		check(inferred_f64, "-1.5", Err(ParseError::InvalidCharacter(0)));
+		check(inferred_f64, ".5", Err(ParseError::InvalidCharacter(0)));
+		check(inferred_f64, " 1.5", Err(ParseError::InvalidCharacter(0)));
+		check(inferred_f64, "1.e5", Err(ParseError::InvalidCharacter(2)));
+		check(inferred_f64, "1.5 ", Err(ParseError::InvalidCharacter(3)));
+		check(inferred_f64, "1.5.5", Err(ParseError::InvalidCharacter(3)));
+		check(inferred_f64, "1e", Err(ParseError::DanglingExponent(1)));
+		check(inferred_f64, "0x1p3", Err(ParseError::InvalidCharacter(1)));
+	}
+	
+	#[test]
+	pub fn underscore_tests() {
+		check(inferred_f64, "1_000.000_1", Ok((1000.0001, None)));
+		check(inferred_f64, "1_.5", Ok((1.5, None)));
+		check(inferred_f64, "1.5__", Ok((1.5, None)));
+		check(inferred_f64, "1__e_5_", Ok((100000.0, None)));
+		check(inferred_f64, "1e+_5", Ok((100000.0, None)));
+		check(inferred_f64, "_1.5", Err(ParseError::InvalidCharacter(0)));
+		check(inferred_f64, "1._5", Err(ParseError::InvalidCharacter(2)));
+		check(inferred_f64, "1e_", Err(ParseError::DanglingExponent(1)));
+	}
+	
+	#[test]
+	pub fn suffix_tests() {
+		check(inferred_f32, "0.1", Ok((f64::from(0.1_f32), None)));
+		check(inferred_f32, "1_000.5e-3_f64", Ok((1.0005, Some(F64))));
+		check(inferred_f64, "0.1f32", Ok((f64::from(0.1_f32), Some(F32))));
+		check(inferred_f64, "1.00000017881393432617187499_f32", Ok((f64::from(1.0000001_f32), Some(F32))));
+		check(inferred_f64, "1f32", Ok((1.0, Some(F32))));
+		check(inferred_f64, "1_f64", Ok((1.0, Some(F64))));
+		check(inferred_f64, "0f32", Ok((0.0, Some(F32))));
+		check(inferred_f64, "1e5f32", Ok((100000.0, Some(F32))));
+		check(inferred_f64, "1e39_f32", Ok((f64::INFINITY, Some(F32))));
+		check(inferred_f64, "1.f32", Err(ParseError::InvalidCharacter(2)));
+		check(inferred_f64, "1.5f16", Err(ParseError::InvalidCharacter(3)));
+		check(inferred_f64, "1.5_f", Err(ParseError::InvalidCharacter(4)));
+		check(inferred_f64, "1e+f32", Err(ParseError::DanglingExponent(1)));
+	}
+	
+	#[test]
+	pub fn integer_tests() {
+		check(inferred_f64, "1", Err(ParseError::MissingExponent(1)));
+		check(inferred_f64, "1_000_", Err(ParseError::MissingExponent(6)));
+	}
+	
+	fn inferred_f64(input: &str) -> Result<(f64, Option<FloatType>), ParseError> {
+		parse_literal(input, F64).map(|literal| (literal.value, literal.suffix))
+	}
+	
+	fn inferred_f32(input: &str) -> Result<(f64, Option<FloatType>), ParseError> {
+		parse_literal(input, F32).map(|literal| (literal.value, literal.suffix))
+	}
+}
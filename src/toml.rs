@@ -0,0 +1,141 @@
+//! Floats in TOML 1.0.
+
+use super::{GroupSeparators, ParseError, ParseOptions, Parser, SpecialValues};
+
+static OPTIONS : ParseOptions = ParseOptions {
+	special_values: SpecialValues {
+		infinity: &["inf"],
+		nan: &["nan"],
+		signaling_nan: &[],
+		nan_payload: false,
+		ignore_case: false
+	},
+	group_separators: GroupSeparators::UNDERSCORE,
+	skip_whitespace: false,
+	leading_decimal_point: false,
+	trailing_decimal_point: false,
+	leading_zeros: false,
+	exponent_backoff: false,
+	..ParseOptions::new()
+};
+
+/// Parses a float with the grammar of TOML 1.0.
+///
+/// * The number has an optional `+` or `-` sign and an integer part
+///   without leading zeros, followed by a fraction, an exponent or both.
+///   A number with neither, like `1`, is a TOML integer and is reported as
+///   `ParseError::MissingExponent`.
+/// * The decimal point must have digits on both sides, so `.5` and `5.`
+///   are rejected.
+/// * Underscores are only allowed between two digits, like `1_000.000_1`.
+/// * `inf` and `nan` are accepted in lower case with an optional sign.
+/// * Whitespace is not skipped, and nothing may follow the number.
+///
+/// Errors carry the byte offset of the first character that does not fit
+/// the grammar.
+///
+/// ```
+/// use strtod::toml::parse_float;
+/// use strtod::ParseError;
+///
+/// assert_eq!(parse_float("+1_000.5e-3"), Ok(1.0005));
+/// assert_eq!(parse_float("-inf"), Ok(f64::NEG_INFINITY));
+/// assert_eq!(parse_float("03.14"), Err(ParseError::LeadingZero(0)));
+/// assert_eq!(parse_float("1._5"), Err(ParseError::MisplacedSeparator(2)));
+/// ```
+pub fn parse_float(input: &str) -> Result<f64, ParseError> {
+	let input = input.as_bytes();
+	
+	let mut parser = Parser::new(&OPTIONS);
+	let d = parser.parse_strict(input, false)?;
+	
+	let number = &input[parser.start..];
+	
+	if !number[0].is_ascii_alphabetic() && !number.iter().any(|&c| matches!(c, b'.' | b'e' | b'E')) {
+		return Err(ParseError::MissingExponent(input.len()));
+	}
+	
+	Ok(d)
+}
+
+#[cfg(test)]
+mod test {
+	use super::parse_float;
+	use test::check;
+	use ParseError;
+	use std::f64;
+	
+	#[test]
+	#[allow(clippy::approx_constant)]
+	pub fn decimal_tests() {
+		check(parse_float, "+1.0", Ok(1.0));
+		check(parse_float, "3.1415", Ok(3.1415));
+		check(parse_float, "-0.01", Ok(-0.01));
+		check(parse_float, "5e+22", Ok(5e+22));
+		check(parse_float, "1e06", Ok(1000000.0));
+		check(parse_float, "-2E-2", Ok(-0.02));
+		check(parse_float, "6.626e-34", Ok(6.626e-34));
+		check(parse_float, "0.0", Ok(0.0));
+		check(parse_float, "-0.0", Ok(-0.0));
+		check(parse_float, "+0e0", Ok(0.0));
+		check(parse_float, "1e400", Ok(f64::INFINITY));
+		check(parse_float, "", Err(ParseError::Empty(0)));
+		check(parse_float, "+", Err(ParseError::SignWithoutDigits(1)));
+		check(parse_float, "1e", Err(ParseError::DanglingExponent(1)));
+		check(parse_float, "1e+", Err(ParseError::DanglingExponent(1)));
+		check(parse_float, " 1.5", Err(ParseError::InvalidCharacter(0)));
+		check(parse_float, "1.5 ", Err(ParseError::InvalidCharacter(3)));
+		check(parse_float, "1.5.5", Err(ParseError::InvalidCharacter(3)));
+		check(parse_float, "0x1p3", Err(ParseError::InvalidCharacter(1)));
+	}
+	
+	#[test]
+	pub fn decimal_point_tests() {
+		check(parse_float, ".", Err(ParseError::NoDigits(0)));
+		check(parse_float, ".7", Err(ParseError::NoDigits(0)));
+		check(parse_float, "-.7", Err(ParseError::NoDigits(1)));
+		check(parse_float, "7.", Err(ParseError::NoDigits(2)));
+		check(parse_float, "3.e+20", Err(ParseError::NoDigits(2)));
+	}
+	
+	#[test]
+	pub fn leading_zero_tests() {
+		check(parse_float, "00.5", Err(ParseError::LeadingZero(0)));
+		check(parse_float, "-01e5", Err(ParseError::LeadingZero(1)));
+		check(parse_float, "0_1.5", Err(ParseError::LeadingZero(0)));
+	}
+	
+	#[test]
+	pub fn underscore_tests() {
+		check(parse_float, "224_617.445_991_228", Ok(224617.445991228));
+		check(parse_float, "1e1_0", Ok(10000000000.0));
+		check(parse_float, "0.000_1", Ok(0.0001));
+		check(parse_float, "_1.5", Err(ParseError::MisplacedSeparator(0)));
+		check(parse_float, "1_.5", Err(ParseError::MisplacedSeparator(1)));
+		check(parse_float, "1._5", Err(ParseError::MisplacedSeparator(2)));
+		check(parse_float, "1.5_", Err(ParseError::MisplacedSeparator(3)));
+		check(parse_float, "1__0.5", Err(ParseError::MisplacedSeparator(2)));
+		check(parse_float, "1_e5", Err(ParseError::MisplacedSeparator(1)));
+		check(parse_float, "1e_5", Err(ParseError::MisplacedSeparator(2)));
+		check(parse_float, "1e+_5", Err(ParseError::MisplacedSeparator(3)));
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		check(parse_float, "inf", Ok(f64::INFINITY));
+		check(parse_float, "+inf", Ok(f64::INFINITY));
+		check(parse_float, "-inf", Ok(f64::NEG_INFINITY));
+		check(parse_float, "nan", Ok(f64::NAN));
+		check(parse_float, "+nan", Ok(f64::NAN));
+		check(parse_float, "-nan", Ok(-f64::NAN));
+		check(parse_float, "Inf", Err(ParseError::InvalidCharacter(0)));
+		check(parse_float, "infinity", Err(ParseError::InvalidCharacter(3)));
+	}
+	
+	#[test]
+	pub fn integer_tests() {
+		check(parse_float, "1", Err(ParseError::MissingExponent(1)));
+		check(parse_float, "-0", Err(ParseError::MissingExponent(2)));
+		check(parse_float, "1_000", Err(ParseError::MissingExponent(5)));
+	}
+}
@@ -0,0 +1,129 @@
+//! Float resolution in the core schema of YAML 1.2.
+
+use super::{ParseOptions, Parser, SpecialValues};
+
+static OPTIONS : ParseOptions = ParseOptions {
+	special_values: SpecialValues {
+		infinity: &[".inf", ".Inf", ".INF"],
+		nan: &[".nan", ".NaN", ".NAN"],
+		signaling_nan: &[],
+		nan_payload: false,
+		ignore_case: false
+	},
+	skip_whitespace: false,
+	exponent_backoff: false,
+	..ParseOptions::new()
+};
+
+/// Resolves a plain scalar to a float like the core schema of YAML 1.2.
+///
+/// Returns `None` unless the whole scalar matches one of the regular
+/// expressions of the `tag:yaml.org,2002:float` tag:
+///
+/// * `[-+]? ( \. [0-9]+ | [0-9]+ ( \. [0-9]* )? ) ( [eE] [-+]? [0-9]+ )?`
+/// * `[-+]? ( \.inf | \.Inf | \.INF )`
+/// * `\.nan | \.NaN | \.NAN`
+///
+/// Scalars that match the `int` tag first, like `1` or `-0`, are not
+/// floats, and neither are `0x1A`, `1.2.3`, `1e` or `1_000`. Note that
+/// `1.10` is a float by this schema, which is why a version number like
+/// that has to be quoted to stay a string.
+///
+/// The value is rounded correctly, and `.nan` is the default NaN.
+///
+/// ```
+/// use strtod::yaml::resolve_float;
+///
+/// assert_eq!(resolve_float("1.10"), Some(1.1));
+/// assert_eq!(resolve_float("-.Inf"), Some(f64::NEG_INFINITY));
+/// assert_eq!(resolve_float("1.10.2"), None);
+/// assert_eq!(resolve_float("10"), None);
+/// ```
+pub fn resolve_float(scalar: &str) -> Option<f64> {
+	let input = scalar.as_bytes();
+	
+	let mut parser = Parser::new(&OPTIONS);
+	let d = parser.parse_strict(input, false).ok()?;
+	
+	let number = &input[parser.start..];
+	
+	if d.is_nan() && parser.start != 0 {
+		return None;
+	}
+	
+	if !number.iter().any(|&c| matches!(c, b'.' | b'e' | b'E')) {
+		// an integer
+		return None;
+	}
+	
+	Some(d)
+}
+
+#[cfg(test)]
+mod test {
+	use super::resolve_float;
+	use test::check;
+	use std::f64;
+	
+	#[test]
+	pub fn decimal_tests() {
+		check(resolve_float, "1.5", Some(1.5));
+		check(resolve_float, "1.10", Some(1.1));
+		check(resolve_float, "-1.", Some(-1.0));
+		check(resolve_float, "+.5", Some(0.5));
+		check(resolve_float, "0.", Some(0.0));
+		check(resolve_float, "-0.0", Some(-0.0));
+		check(resolve_float, "007.5", Some(7.5));
+		check(resolve_float, "1e400", Some(f64::INFINITY));
+		check(resolve_float, "-1e-400", Some(-0.0));
+		check(resolve_float, "", None);
+		check(resolve_float, ".", None);
+		check(resolve_float, "-.", None);
+		check(resolve_float, "1.2.3", None);
+		check(resolve_float, "1.10.2", None);
+		check(resolve_float, "1_000.5", None);
+		check(resolve_float, " 1.5", None);
+		check(resolve_float, "1.5 ", None);
+		check(resolve_float, "1.5\n", None);
+		check(resolve_float, "0x1p3", None);
+	}
+	
+	#[test]
+	pub fn exponent_tests() {
+		check(resolve_float, "1e5", Some(100000.0));
+		check(resolve_float, "1E-05", Some(1e-05));
+		check(resolve_float, "+12e03", Some(12000.0));
+		check(resolve_float, "2.5e+1", Some(25.0));
+		check(resolve_float, ".5e1", Some(5.0));
+		check(resolve_float, "e5", None);
+		check(resolve_float, ".e5", None);
+		check(resolve_float, "1e", None);
+		check(resolve_float, "1e+", None);
+		check(resolve_float, "1.e", None);
+	}
+	
+	#[test]
+	pub fn integer_tests() {
+		check(resolve_float, "1", None);
+		check(resolve_float, "-0", None);
+		check(resolve_float, "+12", None);
+		check(resolve_float, "0o17", None);
+		check(resolve_float, "0x1A", None);
+	}
+	
+	#[test]
+	pub fn special_value_tests() {
+		check(resolve_float, ".inf", Some(f64::INFINITY));
+		check(resolve_float, "+.Inf", Some(f64::INFINITY));
+		check(resolve_float, "-.INF", Some(f64::NEG_INFINITY));
+		check(resolve_float, ".nan", Some(f64::NAN));
+		check(resolve_float, ".NaN", Some(f64::NAN));
+		check(resolve_float, ".NAN", Some(f64::NAN));
+		check(resolve_float, "inf", None);
+		check(resolve_float, ".iNf", None);
+		check(resolve_float, ".infinity", None);
+		check(resolve_float, "-.nan", None);
+		check(resolve_float, "+.NaN", None);
+		check(resolve_float, "nan", None);
+	}
+}